@@ -0,0 +1,238 @@
+use blink_client::accounts::fetch_config;
+use blink_client::state::{
+    Config, DisputePolicy, ExposureLimits, FeeSchedule, FeeTier, ProposalAction,
+};
+use blink_client::{instructions, pda};
+use clap::{Args, Subcommand};
+use serde_json::{json, Value};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::commands::variant_name;
+use crate::context::{describe, Context};
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    Show,
+    /// Change the config through a governance proposal: queue it with
+    /// `--queue`, then run the same command without it once the governance
+    /// delay has passed. Requires the admin role.
+    Set {
+        /// Id of the proposal carrying the change.
+        #[arg(long)]
+        proposal_id: u64,
+        /// Queue the proposal instead of executing it.
+        #[arg(long)]
+        queue: bool,
+        #[command(subcommand)]
+        change: ConfigChange,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigChange {
+    /// Fee split in basis points of each new market's pool.
+    FeeSchedule(FeeScheduleArgs),
+    /// Replace the volume-based fee discounts.
+    FeeTiers {
+        /// `MIN_VOLUME:DISCOUNT_BPS`, repeatable.
+        #[arg(long = "tier", value_parser = parse_fee_tier)]
+        tiers: Vec<FeeTier>,
+    },
+    /// Bond posted by creators without the market creator role.
+    CreationBond { lamports: u64 },
+    /// Wait between queueing and executing a proposal.
+    GovernanceDelay { seconds: i64 },
+    /// Caps on bets into new markets; zero means unlimited.
+    ExposureLimits(ExposureLimitsArgs),
+    /// How resolutions can be challenged.
+    DisputePolicy {
+        /// Seconds a resolution can be challenged for.
+        #[arg(long)]
+        window: i64,
+        /// Lamports a challenger must post.
+        #[arg(long)]
+        bond: u64,
+    },
+}
+
+#[derive(Args)]
+pub struct FeeScheduleArgs {
+    #[arg(long)]
+    protocol_bps: u16,
+    #[arg(long)]
+    creator_bps: u16,
+    #[arg(long)]
+    referrer_bps: u16,
+    #[arg(long)]
+    resolver_bps: u16,
+}
+
+#[derive(Args)]
+pub struct ExposureLimitsArgs {
+    #[arg(long, default_value_t = 0)]
+    max_bet_per_tx: u64,
+    #[arg(long, default_value_t = 0)]
+    max_position_per_user: u64,
+    #[arg(long, default_value_t = 0)]
+    max_market_tvl: u64,
+    #[arg(long, default_value_t = 0)]
+    max_imbalance_bps: u64,
+}
+
+fn parse_fee_tier(tier: &str) -> Result<FeeTier, String> {
+    let (min_volume, discount_bps) = tier
+        .split_once(':')
+        .ok_or("expected MIN_VOLUME:DISCOUNT_BPS")?;
+    Ok(FeeTier {
+        min_volume: min_volume.parse().map_err(|error| format!("{error}"))?,
+        discount_bps: discount_bps.parse().map_err(|error| format!("{error}"))?,
+    })
+}
+
+impl ConfigChange {
+    fn action(&self) -> ProposalAction {
+        match self {
+            ConfigChange::FeeSchedule(args) => ProposalAction::UpdateFeeSchedule {
+                fee_schedule: args.into(),
+            },
+            ConfigChange::FeeTiers { tiers } => ProposalAction::UpdateFeeTiers {
+                fee_tiers: tiers.clone(),
+            },
+            ConfigChange::CreationBond { lamports } => ProposalAction::UpdateCreationBond {
+                creation_bond: *lamports,
+            },
+            ConfigChange::GovernanceDelay { seconds } => ProposalAction::UpdateGovernanceDelay {
+                governance_delay: *seconds,
+            },
+            ConfigChange::ExposureLimits(args) => ProposalAction::UpdateExposureLimits {
+                exposure_limits: args.into(),
+            },
+            ConfigChange::DisputePolicy { window, bond } => ProposalAction::UpdateDisputePolicy {
+                dispute_policy: DisputePolicy {
+                    window: *window,
+                    bond: *bond,
+                },
+            },
+        }
+    }
+
+    fn execute(&self, authority: &Pubkey, proposal_id: u64) -> Instruction {
+        match self.action() {
+            ProposalAction::UpdateFeeSchedule { fee_schedule } => {
+                instructions::update_fee_schedule(authority, proposal_id, fee_schedule)
+            }
+            ProposalAction::UpdateFeeTiers { fee_tiers } => {
+                instructions::update_fee_tiers(authority, proposal_id, fee_tiers)
+            }
+            ProposalAction::UpdateCreationBond { creation_bond } => {
+                instructions::update_creation_bond(authority, proposal_id, creation_bond)
+            }
+            ProposalAction::UpdateGovernanceDelay { governance_delay } => {
+                instructions::update_governance_delay(authority, proposal_id, governance_delay)
+            }
+            ProposalAction::UpdateExposureLimits { exposure_limits } => {
+                instructions::update_exposure_limits(authority, proposal_id, exposure_limits)
+            }
+            ProposalAction::UpdateDisputePolicy { dispute_policy } => {
+                instructions::update_dispute_policy(authority, proposal_id, dispute_policy)
+            }
+            _ => unreachable!("config changes only produce config actions"),
+        }
+    }
+}
+
+impl From<&FeeScheduleArgs> for FeeSchedule {
+    fn from(args: &FeeScheduleArgs) -> Self {
+        FeeSchedule {
+            protocol_bps: args.protocol_bps,
+            creator_bps: args.creator_bps,
+            referrer_bps: args.referrer_bps,
+            resolver_bps: args.resolver_bps,
+        }
+    }
+}
+
+impl From<&ExposureLimitsArgs> for ExposureLimits {
+    fn from(args: &ExposureLimitsArgs) -> Self {
+        ExposureLimits {
+            max_bet_per_tx: args.max_bet_per_tx,
+            max_position_per_user: args.max_position_per_user,
+            max_market_tvl: args.max_market_tvl,
+            max_imbalance_bps: args.max_imbalance_bps,
+        }
+    }
+}
+
+pub fn config_json(config: &Config) -> Value {
+    let fees = &config.fee_schedule;
+    let limits = &config.exposure_limits;
+    json!({
+        "address": pda::config_address().0.to_string(),
+        "authority": config.authority.to_string(),
+        "fee_schedule": {
+            "protocol_bps": fees.protocol_bps,
+            "creator_bps": fees.creator_bps,
+            "referrer_bps": fees.referrer_bps,
+            "resolver_bps": fees.resolver_bps,
+        },
+        "fee_tiers": config.fee_tiers.iter().map(|tier| json!({
+            "min_volume": tier.min_volume,
+            "discount_bps": tier.discount_bps,
+        })).collect::<Vec<_>>(),
+        "creation_bond": config.creation_bond,
+        "governance_delay": config.governance_delay,
+        "exposure_limits": {
+            "max_bet_per_tx": limits.max_bet_per_tx,
+            "max_position_per_user": limits.max_position_per_user,
+            "max_market_tvl": limits.max_market_tvl,
+            "max_imbalance_bps": limits.max_imbalance_bps,
+        },
+        "dispute_policy": {
+            "window": config.dispute_policy.window,
+            "bond": config.dispute_policy.bond,
+        },
+        "pause": {
+            "paused": config.pause.paused,
+            "reason": variant_name(config.pause.reason),
+            "allow_claims": config.pause.allow_claims,
+        },
+    })
+}
+
+pub fn run(context: &Context, command: ConfigCommand) -> anyhow::Result<Value> {
+    match command {
+        ConfigCommand::Show => {
+            let config = fetch_config(&context.rpc).map_err(describe)?;
+            Ok(config_json(&config))
+        }
+        ConfigCommand::Set {
+            proposal_id,
+            queue,
+            change,
+        } => {
+            let authority = context.signer_pubkey()?;
+            let ix = if queue {
+                instructions::queue_proposal(&authority, proposal_id, change.action())
+            } else {
+                change.execute(&authority, proposal_id)
+            };
+            let mut output = context.send(&[ix])?;
+            output["proposal"] = pda::proposal_address(proposal_id).0.to_string().into();
+            Ok(output)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_tiers_parse_from_volume_and_discount() {
+        let tier = parse_fee_tier("1000000000:250").unwrap();
+        assert_eq!((tier.min_volume, tier.discount_bps), (1_000_000_000, 250));
+        assert!(parse_fee_tier("1000000000").is_err());
+        assert!(parse_fee_tier("a:250").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Context as _;
+use blink_client::accounts::fetch_all;
+use blink_client::state::{OracleSource, PriceFeedConfig, PriceFeedParams, ProposalAction};
+use blink_client::{instructions, pda};
+use clap::Subcommand;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::commands::variant_name;
+use crate::context::{describe, Context};
+
+#[derive(Subcommand)]
+pub enum FeedCommand {
+    /// Register a Pyth price feed. Requires the admin role.
+    Add {
+        price_feed: Pubkey,
+        #[arg(long)]
+        symbol: String,
+        #[arg(long)]
+        decimals: u8,
+        /// Maximum age of a price, in seconds.
+        #[arg(long, default_value_t = 60)]
+        max_staleness: u64,
+        /// Maximum confidence interval, in basis points of the price.
+        #[arg(long, default_value_t = 100)]
+        max_confidence_bps: u16,
+    },
+    /// List registered price feeds.
+    List,
+    /// Let markets be created on a feed again through a governance proposal:
+    /// queue it with `--queue`, then run the same command without it once the
+    /// governance delay has passed. Requires the admin role.
+    Enable {
+        price_feed: Pubkey,
+        /// Id of the proposal carrying the change.
+        #[arg(long)]
+        proposal_id: u64,
+        /// Queue the proposal instead of executing it.
+        #[arg(long)]
+        queue: bool,
+    },
+    /// Stop new markets from being created on a feed. Requires the admin role.
+    Disable { price_feed: Pubkey },
+}
+
+pub fn feed_json(address: &Pubkey, config: &PriceFeedConfig) -> Value {
+    json!({
+        "address": address.to_string(),
+        "price_feed": config.price_feed.to_string(),
+        "symbol": config.symbol,
+        "decimals": config.decimals,
+        "oracle_source": variant_name(config.oracle_source),
+        "max_staleness": config.max_staleness,
+        "max_confidence_bps": config.max_confidence_bps,
+        "enabled": config.enabled,
+        "open_markets": config.open_markets,
+    })
+}
+
+pub fn run(context: &Context, command: FeedCommand) -> anyhow::Result<Value> {
+    match command {
+        FeedCommand::Add {
+            price_feed,
+            symbol,
+            decimals,
+            max_staleness,
+            max_confidence_bps,
+        } => {
+            let params = PriceFeedParams {
+                symbol,
+                decimals,
+                oracle_source: OracleSource::Pyth,
+                max_staleness,
+                max_confidence_bps,
+            };
+            let ix =
+                instructions::initialize_price_feed(&context.signer_pubkey()?, &price_feed, params);
+            let mut output = context.send(&[ix])?;
+            output["price_feed_config"] = pda::price_feed_config_address(&price_feed)
+                .0
+                .to_string()
+                .into();
+            Ok(output)
+        }
+        FeedCommand::List => {
+            let mut feeds = fetch_all::<PriceFeedConfig>(&context.rpc)
+                .map_err(describe)
+                .context("listing price feeds")?;
+            feeds.sort_by(|(_, a), (_, b)| a.symbol.cmp(&b.symbol));
+            Ok(feeds
+                .iter()
+                .map(|(address, config)| feed_json(address, config))
+                .collect())
+        }
+        FeedCommand::Enable {
+            price_feed,
+            proposal_id,
+            queue,
+        } => {
+            let authority = context.signer_pubkey()?;
+            let ix = if queue {
+                let action = ProposalAction::EnablePriceFeed { price_feed };
+                instructions::queue_proposal(&authority, proposal_id, action)
+            } else {
+                instructions::enable_price_feed(&authority, &price_feed, proposal_id)
+            };
+            let mut output = context.send(&[ix])?;
+            output["proposal"] = pda::proposal_address(proposal_id).0.to_string().into();
+            Ok(output)
+        }
+        FeedCommand::Disable { price_feed } => {
+            let ix = instructions::disable_price_feed(&context.signer_pubkey()?, &price_feed);
+            context.send(&[ix])
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use blink_client::instructions;
+use clap::Subcommand;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::context::Context;
+
+#[derive(Subcommand)]
+pub enum FeesCommand {
+    /// Withdraw the protocol's fees from resolved markets to the team wallet
+    /// in one transaction. Requires the treasury role.
+    Withdraw {
+        #[arg(required = true)]
+        markets: Vec<Pubkey>,
+    },
+}
+
+pub fn run(context: &Context, command: FeesCommand) -> anyhow::Result<Value> {
+    match command {
+        FeesCommand::Withdraw { markets } => {
+            let authority = context.signer_pubkey()?;
+            let ix = match markets.as_slice() {
+                [market] => instructions::withdraw_team_fee(&authority, market),
+                markets => instructions::withdraw_team_fees(&authority, markets),
+            };
+            context.send(&[ix])
+        }
+    }
+}
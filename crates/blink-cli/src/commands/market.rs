@@ -0,0 +1,175 @@
+use anyhow::Context as _;
+use blink_client::accounts::{fetch_market, fetch_markets, MarketFilter};
+use blink_client::state::{Market, MarketStatus, Role, VoidReason};
+use blink_client::{instructions, pda};
+use clap::{Subcommand, ValueEnum};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::commands::variant_name;
+use crate::context::{describe, Context};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StatusArg {
+    Open,
+    Resolved,
+    Voided,
+}
+
+impl From<StatusArg> for MarketStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Open => MarketStatus::Open,
+            StatusArg::Resolved => MarketStatus::Resolved,
+            StatusArg::Voided => MarketStatus::Voided,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum VoidReasonArg {
+    /// The creator set the market up incorrectly; their bond is slashed.
+    CreatorMisconfiguration,
+    /// The oracle could not provide a usable price.
+    OracleFailure,
+}
+
+impl From<VoidReasonArg> for VoidReason {
+    fn from(reason: VoidReasonArg) -> Self {
+        match reason {
+            VoidReasonArg::CreatorMisconfiguration => VoidReason::CreatorMisconfiguration,
+            VoidReasonArg::OracleFailure => VoidReason::OracleFailure,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum MarketCommand {
+    /// Create a market on a registered price feed. Holders of the market
+    /// creator role skip the creation bond.
+    Create {
+        #[arg(long)]
+        price_feed: Pubkey,
+        /// Unique per creator; part of the market's address.
+        #[arg(long)]
+        market_id: u64,
+        /// In seconds.
+        #[arg(long)]
+        duration: u64,
+    },
+    /// List markets in their current layout.
+    List {
+        #[arg(long)]
+        status: Option<StatusArg>,
+        #[arg(long)]
+        authority: Option<Pubkey>,
+        #[arg(long)]
+        price_feed: Option<Pubkey>,
+    },
+    Show {
+        market: Pubkey,
+    },
+    /// Settle a market at the feed's current price. Requires the resolver
+    /// role.
+    Resolve {
+        market: Pubkey,
+    },
+    /// Void a market and refund its bettors. Requires the admin role.
+    Void {
+        market: Pubkey,
+        #[arg(long, value_enum)]
+        reason: VoidReasonArg,
+    },
+}
+
+pub fn market_json(address: &Pubkey, market: &Market) -> Value {
+    json!({
+        "address": address.to_string(),
+        "market_id": market.market_id,
+        "authority": market.authority.to_string(),
+        "symbol": market.symbol(),
+        "price_feed": market.feed_id.to_string(),
+        "status": variant_name(market.status()),
+        "start_time": market.start_time,
+        "end_time": market.start_time + market.duration,
+        "initial_price": market.initial_price,
+        "final_price": market.winning_outcome().map(|_| market.final_price),
+        "winning_outcome": market.winning_outcome().map(|yes| if yes { "yes" } else { "no" }),
+        "void_reason": market.void_reason().map(variant_name),
+        "total_yes_shares": market.total_yes_shares,
+        "total_no_shares": market.total_no_shares,
+        "resolver": market.resolver().map(|resolver| resolver.to_string()),
+        "dispute_deadline": market.dispute_deadline,
+        "disputed": market.disputed != 0,
+        "team_fee_unlock_time": market.team_fee_unlock_time,
+        "team_fee_paid": market.team_fee_paid != 0,
+        "creator_bond": market.creator_bond,
+        "paused": market.pause().paused,
+    })
+}
+
+pub fn run(context: &Context, command: MarketCommand) -> anyhow::Result<Value> {
+    match command {
+        MarketCommand::Create {
+            price_feed,
+            market_id,
+            duration,
+        } => {
+            let authority = context.signer_pubkey()?;
+            let approved_creator =
+                context.account_exists(&pda::role_address(Role::MarketCreator, &authority).0)?;
+            let ix = instructions::create_market(
+                &authority,
+                market_id,
+                duration,
+                &price_feed,
+                approved_creator,
+            );
+            let mut output = context.send(&[ix])?;
+            output["market"] = pda::market_address(&authority, market_id)
+                .0
+                .to_string()
+                .into();
+            Ok(output)
+        }
+        MarketCommand::List {
+            status,
+            authority,
+            price_feed,
+        } => {
+            let filter = MarketFilter {
+                status: status.map(Into::into),
+                authority,
+                price_feed,
+            };
+            let mut markets = fetch_markets(&context.rpc, &filter)
+                .map_err(describe)
+                .context("listing markets")?;
+            markets.sort_by_key(|(_, market)| market.start_time);
+            Ok(markets
+                .iter()
+                .map(|(address, market)| market_json(address, market))
+                .collect())
+        }
+        MarketCommand::Show { market } => {
+            let state = fetch_market(&context.rpc, &market).map_err(describe)?;
+            Ok(market_json(&market, &state))
+        }
+        MarketCommand::Resolve { market } => {
+            let state = fetch_market(&context.rpc, &market).map_err(describe)?;
+            let ix =
+                instructions::resolve_market(&context.signer_pubkey()?, &market, &state.feed_id);
+            context.send(&[ix])
+        }
+        MarketCommand::Void { market, reason } => {
+            let state = fetch_market(&context.rpc, &market).map_err(describe)?;
+            let ix = instructions::void_market(
+                &context.signer_pubkey()?,
+                &market,
+                &state.feed_id,
+                reason.into(),
+            );
+            context.send(&[ix])
+        }
+    }
+}
@@ -0,0 +1,19 @@
+pub mod config;
+pub mod feed;
+pub mod fees;
+pub mod market;
+
+use std::fmt::Debug;
+
+/// Enum values as snake_case strings, for both output formats.
+pub fn variant_name(value: impl Debug) -> String {
+    let name = format!("{value:?}");
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, char) in name.chars().enumerate() {
+        if char.is_ascii_uppercase() && index > 0 {
+            snake.push('_');
+        }
+        snake.push(char.to_ascii_lowercase());
+    }
+    snake
+}
@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context as _};
+use blink_client::errors::program_error;
+use blink_client::ClientError;
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+/// What every command needs: an RPC connection and, for commands that send
+/// transactions, the signer. The keypair is only read when a transaction is
+/// built, so read-only commands work without one.
+pub struct Context {
+    pub rpc: RpcClient,
+    keypair: PathBuf,
+    dry_run: bool,
+}
+
+fn rpc_url(url: &str) -> &str {
+    match url {
+        "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+}
+
+fn default_keypair() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".config/solana/id.json")
+}
+
+/// Names the program error behind a failed transaction, if there is one.
+pub fn describe(error: ClientError) -> anyhow::Error {
+    match error.program_error() {
+        Some(code) => anyhow!("{}: {}", code.name(), code),
+        None => error.into(),
+    }
+}
+
+fn describe_transaction_error(error: TransactionError) -> anyhow::Error {
+    match program_error(&error) {
+        Some(code) => anyhow!("{}: {}", code.name(), code),
+        None => error.into(),
+    }
+}
+
+impl Context {
+    pub fn new(url: &str, keypair: Option<&Path>, dry_run: bool) -> anyhow::Result<Self> {
+        Ok(Context {
+            rpc: RpcClient::new_with_commitment(
+                rpc_url(url).to_string(),
+                CommitmentConfig::confirmed(),
+            ),
+            keypair: keypair.map_or_else(default_keypair, Path::to_path_buf),
+            dry_run,
+        })
+    }
+
+    pub fn signer(&self) -> anyhow::Result<Keypair> {
+        read_keypair_file(&self.keypair)
+            .map_err(|error| anyhow!("{error}"))
+            .with_context(|| format!("reading keypair {}", self.keypair.display()))
+    }
+
+    pub fn signer_pubkey(&self) -> anyhow::Result<Pubkey> {
+        Ok(self.signer()?.pubkey())
+    }
+
+    pub fn account_exists(&self, address: &Pubkey) -> anyhow::Result<bool> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())
+            .map_err(ClientError::from)
+            .map_err(describe)?;
+        Ok(account.value.is_some())
+    }
+
+    /// Signs and sends `instructions` in one transaction, or simulates it
+    /// under `--dry-run`.
+    pub fn send(&self, instructions: &[Instruction]) -> anyhow::Result<Value> {
+        let signer = self.signer()?;
+        let blockhash = self
+            .rpc
+            .get_latest_blockhash()
+            .map_err(ClientError::from)
+            .map_err(describe)?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[&signer],
+            blockhash,
+        );
+
+        if self.dry_run {
+            let simulation = self
+                .rpc
+                .simulate_transaction(&transaction)
+                .map_err(ClientError::from)
+                .map_err(describe)?
+                .value;
+            if let Some(error) = simulation.err {
+                let logs = simulation.logs.unwrap_or_default().join("\n");
+                return Err(
+                    describe_transaction_error(error).context(format!("simulation failed\n{logs}"))
+                );
+            }
+            return Ok(json!({
+                "simulated": true,
+                "units_consumed": simulation.units_consumed,
+                "logs": simulation.logs.unwrap_or_default(),
+            }));
+        }
+
+        let signature = self
+            .rpc
+            .send_and_confirm_transaction(&transaction)
+            .map_err(ClientError::from)
+            .map_err(describe)?;
+        Ok(json!({ "signature": signature.to_string() }))
+    }
+}
@@ -0,0 +1,98 @@
+//! Admin and operator CLI for the blink program.
+//!
+//! Every command that sends a transaction takes `--dry-run` to simulate it
+//! instead, and every command takes `--json` for machine-readable output.
+
+mod commands;
+mod context;
+mod output;
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::commands::{config, feed, fees, market};
+use crate::context::Context;
+
+#[derive(Parser)]
+#[command(name = "blink-cli", version, about)]
+struct Cli {
+    /// RPC endpoint, or one of localhost, devnet, testnet and mainnet-beta.
+    #[arg(short, long, global = true, default_value = "localhost")]
+    url: String,
+    /// Keypair that signs and pays for transactions. Defaults to the Solana
+    /// CLI's keypair.
+    #[arg(short, long, global = true)]
+    keypair: Option<PathBuf>,
+    /// Print JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+    /// Simulate transactions instead of sending them.
+    #[arg(long, global = true)]
+    dry_run: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Register, list and disable price feeds.
+    #[command(subcommand)]
+    Feed(feed::FeedCommand),
+    /// Create, inspect, resolve and void markets.
+    #[command(subcommand)]
+    Market(market::MarketCommand),
+    /// Withdraw protocol fees.
+    #[command(subcommand)]
+    Fees(fees::FeesCommand),
+    /// Show or update the program config.
+    #[command(subcommand)]
+    Config(config::ConfigCommand),
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let context = Context::new(&cli.url, cli.keypair.as_deref(), cli.dry_run)?;
+
+    let output = match cli.command {
+        Command::Feed(command) => feed::run(&context, command)?,
+        Command::Market(command) => market::run(&context, command)?,
+        Command::Fees(command) => fees::run(&context, command)?,
+        Command::Config(command) => config::run(&context, command)?,
+    };
+    output::print(&output, cli.json);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn arguments_are_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn global_flags_follow_subcommands() {
+        let cli = Cli::try_parse_from([
+            "blink-cli",
+            "market",
+            "list",
+            "--status",
+            "open",
+            "--json",
+            "--dry-run",
+        ])
+        .unwrap();
+
+        assert!(cli.json && cli.dry_run);
+        assert!(matches!(
+            cli.command,
+            Command::Market(market::MarketCommand::List { .. })
+        ));
+    }
+}
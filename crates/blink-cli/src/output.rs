@@ -0,0 +1,90 @@
+//! Commands build a JSON value; this prints it as JSON or as indented
+//! `key: value` text.
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+pub fn print(value: &Value, json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(value).expect("values serialize")
+        );
+    } else {
+        let mut text = String::new();
+        render(&mut text, value, 0);
+        print!("{text}");
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn render(out: &mut String, value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                match value {
+                    Value::Object(_) => {
+                        let _ = writeln!(out, "{pad}{key}:");
+                        render(out, value, indent + 1);
+                    }
+                    Value::Array(items) if !items.is_empty() => {
+                        let _ = writeln!(out, "{pad}{key}:");
+                        render(out, value, indent + 1);
+                    }
+                    Value::Array(_) => {
+                        let _ = writeln!(out, "{pad}{key}: -");
+                    }
+                    value => {
+                        let _ = writeln!(out, "{pad}{key}: {}", scalar(value));
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                if item.is_object() {
+                    if index > 0 {
+                        out.push('\n');
+                    }
+                    render(out, item, indent);
+                } else {
+                    let _ = writeln!(out, "{pad}{}", scalar(item));
+                }
+            }
+        }
+        value => {
+            let _ = writeln!(out, "{pad}{}", scalar(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn renders_nested_values_as_indented_text() {
+        let mut text = String::new();
+        render(
+            &mut text,
+            &json!({ "address": "abc", "fees": { "protocol_bps": 100 }, "tiers": [], "resolver": null }),
+            0,
+        );
+
+        assert_eq!(
+            text,
+            "address: abc\nfees:\n  protocol_bps: 100\ntiers: -\nresolver: -\n"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Fetching and decoding program accounts. `decode_*` work on raw account
+//! data, so they serve RPC responses, websocket notifications and test banks
+//! alike; `fetch_*` wrap them around a blocking `RpcClient`.
+
+use std::mem::size_of;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator, Space};
+use blink_take_2::state::{
+    Config, Market, MarketStatus, PriceFeedConfig, UserPosition, UserProfile,
+    MARKET_AUTHORITY_OFFSET, MARKET_FEED_ID_OFFSET, MARKET_STATUS_OFFSET,
+};
+use blink_take_2::{pda, ID};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+
+use crate::errors::ClientError;
+
+/// Space of a `Market` account, discriminator included.
+pub const MARKET_ACCOUNT_SIZE: usize = 8 + size_of::<Market>();
+
+/// Decodes a Borsh account, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> anchor_lang::Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a zero-copy `Market`. The data need not be aligned.
+pub fn decode_market(data: &[u8]) -> anchor_lang::Result<Market> {
+    if data.len() < 8 || data[..8] != Market::DISCRIMINATOR {
+        return Err(AnchorErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    let body = data
+        .get(8..MARKET_ACCOUNT_SIZE)
+        .ok_or(AnchorErrorCode::AccountDidNotDeserialize)?;
+    Ok(bytemuck::pod_read_unaligned(body))
+}
+
+fn fetch_data(rpc: &RpcClient, address: &Pubkey) -> Result<Vec<u8>, ClientError> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    if account.owner != ID {
+        return Err(ClientError::InvalidAccountData {
+            address: *address,
+            source: Box::new(AnchorErrorCode::AccountOwnedByWrongProgram.into()),
+        });
+    }
+    Ok(account.data)
+}
+
+fn with_address<T>(address: &Pubkey, result: anchor_lang::Result<T>) -> Result<T, ClientError> {
+    result.map_err(|source| ClientError::InvalidAccountData {
+        address: *address,
+        source: Box::new(source),
+    })
+}
+
+/// Fetches and decodes any Borsh account of the program.
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T, ClientError> {
+    let data = fetch_data(rpc, address)?;
+    with_address(address, decode(&data))
+}
+
+pub fn fetch_market(rpc: &RpcClient, address: &Pubkey) -> Result<Market, ClientError> {
+    let data = fetch_data(rpc, address)?;
+    with_address(address, decode_market(&data))
+}
+
+pub fn fetch_config(rpc: &RpcClient) -> Result<Config, ClientError> {
+    fetch(rpc, &pda::config_address().0)
+}
+
+pub fn fetch_price_feed_config(
+    rpc: &RpcClient,
+    price_feed: &Pubkey,
+) -> Result<PriceFeedConfig, ClientError> {
+    fetch(rpc, &pda::price_feed_config_address(price_feed).0)
+}
+
+pub fn fetch_user_position(
+    rpc: &RpcClient,
+    market: &Pubkey,
+    user: &Pubkey,
+) -> Result<UserPosition, ClientError> {
+    fetch(rpc, &pda::user_position_address(market, user).0)
+}
+
+pub fn fetch_user_profile(rpc: &RpcClient, user: &Pubkey) -> Result<UserProfile, ClientError> {
+    fetch(rpc, &pda::user_profile_address(user).0)
+}
+
+fn program_accounts(
+    rpc: &RpcClient,
+    filters: Vec<RpcFilterType>,
+) -> Result<Vec<(Pubkey, Account)>, ClientError> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    Ok(rpc.get_program_accounts_with_config(&ID, config)?)
+}
+
+/// Fetches every Borsh account of type `T` in its current layout; accounts
+/// awaiting migration have a different size and are skipped.
+pub fn fetch_all<T: AccountDeserialize + Discriminator + Space>(
+    rpc: &RpcClient,
+) -> Result<Vec<(Pubkey, T)>, ClientError> {
+    let filters = vec![
+        RpcFilterType::DataSize((8 + T::INIT_SPACE) as u64),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, T::DISCRIMINATOR.to_vec())),
+    ];
+
+    program_accounts(rpc, filters)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, with_address(&address, decode(&account.data))?)))
+        .collect()
+}
+
+/// Narrows `fetch_markets`; unset fields match every market. Only markets
+/// migrated to the current layout are returned.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MarketFilter {
+    pub status: Option<MarketStatus>,
+    pub authority: Option<Pubkey>,
+    pub price_feed: Option<Pubkey>,
+}
+
+impl MarketFilter {
+    fn filters(&self) -> Vec<RpcFilterType> {
+        let mut filters = vec![
+            RpcFilterType::DataSize(MARKET_ACCOUNT_SIZE as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, Market::DISCRIMINATOR.to_vec())),
+        ];
+        if let Some(status) = self.status {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                MARKET_STATUS_OFFSET,
+                vec![status as u8],
+            )));
+        }
+        if let Some(authority) = self.authority {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                MARKET_AUTHORITY_OFFSET,
+                authority.to_bytes().to_vec(),
+            )));
+        }
+        if let Some(price_feed) = self.price_feed {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                MARKET_FEED_ID_OFFSET,
+                price_feed.to_bytes().to_vec(),
+            )));
+        }
+        filters
+    }
+}
+
+pub fn fetch_markets(
+    rpc: &RpcClient,
+    filter: &MarketFilter,
+) -> Result<Vec<(Pubkey, Market)>, ClientError> {
+    program_accounts(rpc, filter.filters())?
+        .into_iter()
+        .map(|(address, account)| {
+            Ok((
+                address,
+                with_address(&address, decode_market(&account.data))?,
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,150 @@
+//! Mapping custom error codes from failed transactions back to the program's
+//! `ErrorCode`, and the errors of the client itself.
+
+use anchor_lang::prelude::Pubkey;
+use blink_take_2::errors::ErrorCode;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+
+pub use anchor_lang::error::ERROR_CODE_OFFSET;
+
+/// Every variant of `ErrorCode`, in declaration order.
+pub const ERRORS: &[ErrorCode] = &[
+    ErrorCode::MarketNotActive,
+    ErrorCode::InsufficientFunds,
+    ErrorCode::MarketAlreadyResolved,
+    ErrorCode::MarketNotResolved,
+    ErrorCode::PriceFetchFailed,
+    ErrorCode::InvalidDuration,
+    ErrorCode::MarketNotExpired,
+    ErrorCode::NotAWinner,
+    ErrorCode::InitialPriceNotSet,
+    ErrorCode::AlreadyClaimed,
+    ErrorCode::InsufficientMarketFunds,
+    ErrorCode::BetAmountTooLow,
+    ErrorCode::InsufficientUserFunds,
+    ErrorCode::MarketAlreadyStarted,
+    ErrorCode::TeamFeeTimelockNotExpired,
+    ErrorCode::PythError,
+    ErrorCode::TryToSerializePriceAccount,
+    ErrorCode::InvalidFeeSchedule,
+    ErrorCode::FeeAlreadyClaimed,
+    ErrorCode::NotMarketResolver,
+    ErrorCode::SelfReferral,
+    ErrorCode::ReferrerMissing,
+    ErrorCode::ReferralAccountMissing,
+    ErrorCode::InvalidReferrer,
+    ErrorCode::InvalidRemainingAccounts,
+    ErrorCode::MarketVoided,
+    ErrorCode::CreatorBondAlreadySettled,
+    ErrorCode::InvalidSymbol,
+    ErrorCode::InvalidPriceFeedParams,
+    ErrorCode::PriceFeedDisabled,
+    ErrorCode::PriceFeedStillEnabled,
+    ErrorCode::StalePrice,
+    ErrorCode::PriceConfidenceTooWide,
+    ErrorCode::InvalidReferralCode,
+    ErrorCode::InvalidFeeTiers,
+    ErrorCode::ProgramPaused,
+    ErrorCode::MarketPaused,
+    ErrorCode::CannotRevokeOwnAdminRole,
+    ErrorCode::ProposalNotQueued,
+    ErrorCode::ProposalTimelockNotExpired,
+    ErrorCode::ProposalActionMismatch,
+    ErrorCode::InvalidGovernanceDelay,
+    ErrorCode::InvalidExposureLimits,
+    ErrorCode::BetTooLarge,
+    ErrorCode::PositionLimitExceeded,
+    ErrorCode::MarketTvlLimitExceeded,
+    ErrorCode::MarketImbalanceLimitExceeded,
+    ErrorCode::InvalidDisputePolicy,
+    ErrorCode::DisputeWindowOpen,
+    ErrorCode::DisputeWindowClosed,
+    ErrorCode::MarketDisputed,
+    ErrorCode::DisputeOutcomeUnchanged,
+    ErrorCode::AccountAlreadyMigrated,
+    ErrorCode::PriceFeedMismatch,
+    ErrorCode::PriceFeedInUse,
+    ErrorCode::UnsupportedAccountVersion,
+];
+
+/// RPC and decoding errors are boxed, both are several hundred bytes.
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error(transparent)]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("account {address} could not be decoded: {source}")]
+    InvalidAccountData {
+        address: Pubkey,
+        source: Box<anchor_lang::error::Error>,
+    },
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(error))
+    }
+}
+
+impl ClientError {
+    /// The program error that failed the transaction, if any.
+    pub fn program_error(&self) -> Option<ErrorCode> {
+        match self {
+            ClientError::Rpc(error) => program_error(&error.get_transaction_error()?),
+            _ => None,
+        }
+    }
+}
+
+/// The program error for a custom error code, or `None` for codes outside
+/// the program's range.
+pub fn error_code(code: u32) -> Option<ErrorCode> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    ERRORS.get(index as usize).copied()
+}
+
+/// The program error that failed a transaction. Failures in other programs
+/// or in Anchor's own checks are not mapped.
+pub fn program_error(error: &TransactionError) -> Option<ErrorCode> {
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => error_code(*code),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_covers_every_error_in_order() {
+        let declared = include_str!("../../../programs/blink-take-2/src/errors.rs")
+            .matches("#[msg(")
+            .count();
+        assert_eq!(ERRORS.len(), declared);
+
+        for (index, error) in ERRORS.iter().enumerate() {
+            assert_eq!(u32::from(*error), ERROR_CODE_OFFSET + index as u32);
+            assert_eq!(
+                error_code(u32::from(*error)).map(u32::from),
+                Some(u32::from(*error))
+            );
+        }
+    }
+
+    #[test]
+    fn codes_outside_the_program_are_not_mapped() {
+        assert!(error_code(0).is_none());
+        assert!(error_code(ERROR_CODE_OFFSET + ERRORS.len() as u32).is_none());
+        assert!(matches!(
+            program_error(&TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + 11)
+            )),
+            Some(ErrorCode::BetAmountTooLow)
+        ));
+        assert!(program_error(&TransactionError::AccountNotFound).is_none());
+    }
+}
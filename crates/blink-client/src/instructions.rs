@@ -0,0 +1,689 @@
+//! One builder per program instruction. Every account the program derives is
+//! derived here from the same seeds, so callers only pass the keys that are
+//! not PDAs: signers, markets, price feeds and the members of a role.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use blink_take_2::constants::TEAM_WALLET;
+use blink_take_2::state::{
+    DisputePolicy, ExposureLimits, FeeSchedule, FeeTier, PauseReason, PriceFeedParams,
+    ProposalAction, Role, VoidReason,
+};
+use blink_take_2::{accounts, instruction, pda};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: blink_take_2::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn config() -> Pubkey {
+    pda::config_address().0
+}
+
+fn role(role: Role, member: &Pubkey) -> Pubkey {
+    pda::role_address(role, member).0
+}
+
+fn proposal(proposal_id: u64) -> Pubkey {
+    pda::proposal_address(proposal_id).0
+}
+
+fn position(market: &Pubkey, user: &Pubkey) -> Pubkey {
+    pda::user_position_address(market, user).0
+}
+
+/// Optional accounts for `place_bet`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BetOptions {
+    /// Records the referrer on the first bet of a position.
+    pub referrer: Option<Pubkey>,
+    /// Must be set on every bet of a position that already has a referral.
+    pub referred: bool,
+    /// Writes a receipt for the bet. This must be the position's current
+    /// `bet_count`.
+    pub receipt_sequence: Option<u64>,
+}
+
+/// Anyone may create a market, escrowing the config's creation bond in it.
+/// Set `approved_creator` when `authority` holds the market creator role to
+/// skip the bond.
+pub fn create_market(
+    authority: &Pubkey,
+    market_id: u64,
+    duration: u64,
+    price_feed: &Pubkey,
+    approved_creator: bool,
+) -> Instruction {
+    build(
+        accounts::CreateMarket {
+            market: pda::market_address(authority, market_id).0,
+            config: config(),
+            price_feed_config: pda::price_feed_config_address(price_feed).0,
+            price_feed: *price_feed,
+            creator_role: approved_creator.then(|| role(Role::MarketCreator, authority)),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMarket {
+            market_id,
+            duration,
+        },
+    )
+}
+
+pub fn create_user(user: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::CreateUser {
+            market: *market,
+            user_position: position(market, user),
+            user: *user,
+            system_program: system_program::ID,
+        },
+        instruction::CreateUser {},
+    )
+}
+
+/// The market's feed registration must already be migrated.
+pub fn migrate_market(payer: &Pubkey, market: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateMarket {
+            market: *market,
+            price_feed_config: pda::price_feed_config_address(price_feed).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateMarket {},
+    )
+}
+
+pub fn migrate_position(payer: &Pubkey, market: &Pubkey, user: &Pubkey) -> Instruction {
+    build(
+        accounts::MigratePosition {
+            market: *market,
+            user_position: position(market, user),
+            user: *user,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigratePosition {},
+    )
+}
+
+pub fn migrate_price_feed(payer: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    build(
+        accounts::MigratePriceFeed {
+            price_feed_config: pda::price_feed_config_address(price_feed).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigratePriceFeed {},
+    )
+}
+
+pub fn set_referral_code(user: &Pubkey, referral_code: String) -> Instruction {
+    build(
+        accounts::SetReferralCode {
+            user_profile: pda::user_profile_address(user).0,
+            user: *user,
+        },
+        instruction::SetReferralCode { referral_code },
+    )
+}
+
+pub fn place_bet(
+    user: &Pubkey,
+    market: &Pubkey,
+    amount: u64,
+    choice: bool,
+    options: BetOptions,
+) -> Instruction {
+    let user_position = position(market, user);
+    let referred = options.referred || options.referrer.is_some();
+
+    build(
+        accounts::PlaceBet {
+            market: *market,
+            user_position,
+            user_profile: pda::user_profile_address(user).0,
+            config: config(),
+            user: *user,
+            system_program: system_program::ID,
+            referrer: options.referrer,
+            referral: referred.then(|| pda::referral_address(&user_position).0),
+            bet_receipt: options
+                .receipt_sequence
+                .map(|sequence| pda::bet_receipt_address(&user_position, sequence).0),
+        },
+        instruction::PlaceBet { amount, choice },
+    )
+}
+
+pub fn resolve_market(resolver: &Pubkey, market: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    build(
+        accounts::ResolveMarket {
+            market: *market,
+            config: config(),
+            resolver_role: role(Role::Resolver, resolver),
+            authority: *resolver,
+            price_feed_config: pda::price_feed_config_address(price_feed).0,
+            price_feed: *price_feed,
+        },
+        instruction::ResolveMarket {},
+    )
+}
+
+pub fn claim_winnings(user: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimWinnings {
+            market: *market,
+            user_position: position(market, user),
+            user_profile: pda::user_profile_address(user).0,
+            config: config(),
+            user: *user,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimWinnings {},
+    )
+}
+
+pub fn withdraw_team_fee(authority: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::WithdrawTeamFee {
+            market: *market,
+            treasury_role: role(Role::Treasury, authority),
+            authority: *authority,
+            team_wallet: TEAM_WALLET,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawTeamFee {},
+    )
+}
+
+/// Withdraws the team fee of every market in one instruction.
+pub fn withdraw_team_fees(authority: &Pubkey, markets: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::WithdrawTeamFees {
+            treasury_role: role(Role::Treasury, authority),
+            authority: *authority,
+            team_wallet: TEAM_WALLET,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawTeamFees {},
+    );
+    ix.accounts.extend(
+        markets
+            .iter()
+            .map(|market| AccountMeta::new(*market, false)),
+    );
+    ix
+}
+
+pub fn cancel_bet(user: &Pubkey, market: &Pubkey, referred: bool) -> Instruction {
+    let user_position = position(market, user);
+
+    build(
+        accounts::CancelBet {
+            market: *market,
+            user_position,
+            user_profile: pda::user_profile_address(user).0,
+            config: config(),
+            user: *user,
+            system_program: system_program::ID,
+            referral: referred.then(|| pda::referral_address(&user_position).0),
+        },
+        instruction::CancelBet {},
+    )
+}
+
+pub fn close_bet_receipt(user: &Pubkey, market: &Pubkey, sequence: u64) -> Instruction {
+    let user_position = position(market, user);
+
+    build(
+        accounts::CloseBetReceipt {
+            market: *market,
+            user_position,
+            bet_receipt: pda::bet_receipt_address(&user_position, sequence).0,
+            user: *user,
+        },
+        instruction::CloseBetReceipt {},
+    )
+}
+
+pub fn claim_creator_fee(authority: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimCreatorFee {
+            market: *market,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimCreatorFee {},
+    )
+}
+
+/// Claims the rewards of every `(market, referred user)` pair in one
+/// instruction.
+pub fn claim_referral_rewards(referrer: &Pubkey, referrals: &[(Pubkey, Pubkey)]) -> Instruction {
+    let mut ix = build(
+        accounts::ClaimReferralRewards {
+            referrer: *referrer,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimReferralRewards {},
+    );
+    for (market, user) in referrals {
+        let referral = pda::referral_address(&position(market, user)).0;
+        ix.accounts.push(AccountMeta::new(*market, false));
+        ix.accounts.push(AccountMeta::new(referral, false));
+    }
+    ix
+}
+
+pub fn claim_resolver_fee(resolver: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimResolverFee {
+            market: *market,
+            resolver: *resolver,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimResolverFee {},
+    )
+}
+
+pub fn initialize_config(
+    authority: &Pubkey,
+    fee_schedule: FeeSchedule,
+    creation_bond: u64,
+    governance_delay: i64,
+    exposure_limits: ExposureLimits,
+    dispute_policy: DisputePolicy,
+) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig {
+            fee_schedule,
+            creation_bond,
+            governance_delay,
+            exposure_limits,
+            dispute_policy,
+        },
+    )
+}
+
+pub fn update_fee_schedule(
+    authority: &Pubkey,
+    proposal_id: u64,
+    fee_schedule: FeeSchedule,
+) -> Instruction {
+    build(
+        accounts::UpdateFeeSchedule {
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdateFeeSchedule { fee_schedule },
+    )
+}
+
+pub fn update_fee_tiers(
+    authority: &Pubkey,
+    proposal_id: u64,
+    fee_tiers: Vec<FeeTier>,
+) -> Instruction {
+    build(
+        accounts::UpdateFeeTiers {
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdateFeeTiers { fee_tiers },
+    )
+}
+
+pub fn update_creation_bond(
+    authority: &Pubkey,
+    proposal_id: u64,
+    creation_bond: u64,
+) -> Instruction {
+    build(
+        accounts::UpdateCreationBond {
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdateCreationBond { creation_bond },
+    )
+}
+
+pub fn update_governance_delay(
+    authority: &Pubkey,
+    proposal_id: u64,
+    governance_delay: i64,
+) -> Instruction {
+    build(
+        accounts::UpdateGovernanceDelay {
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdateGovernanceDelay { governance_delay },
+    )
+}
+
+pub fn update_exposure_limits(
+    authority: &Pubkey,
+    proposal_id: u64,
+    exposure_limits: ExposureLimits,
+) -> Instruction {
+    build(
+        accounts::UpdateExposureLimits {
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdateExposureLimits { exposure_limits },
+    )
+}
+
+pub fn update_dispute_policy(
+    authority: &Pubkey,
+    proposal_id: u64,
+    dispute_policy: DisputePolicy,
+) -> Instruction {
+    build(
+        accounts::UpdateDisputePolicy {
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdateDisputePolicy { dispute_policy },
+    )
+}
+
+pub fn update_market_limits(
+    authority: &Pubkey,
+    market: &Pubkey,
+    proposal_id: u64,
+    exposure_limits: ExposureLimits,
+) -> Instruction {
+    build(
+        accounts::UpdateMarketLimits {
+            market: *market,
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdateMarketLimits { exposure_limits },
+    )
+}
+
+pub fn queue_proposal(authority: &Pubkey, proposal_id: u64, action: ProposalAction) -> Instruction {
+    build(
+        accounts::QueueProposal {
+            proposal: proposal(proposal_id),
+            config: config(),
+            admin_role: role(Role::Admin, authority),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::QueueProposal {
+            proposal_id,
+            action,
+        },
+    )
+}
+
+pub fn cancel_proposal(authority: &Pubkey, proposal_id: u64) -> Instruction {
+    build(
+        accounts::CancelProposal {
+            proposal: proposal(proposal_id),
+            admin_role: role(Role::Admin, authority),
+            authority: *authority,
+        },
+        instruction::CancelProposal {},
+    )
+}
+
+pub fn challenge_outcome(challenger: &Pubkey, market: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    build(
+        accounts::ChallengeOutcome {
+            market: *market,
+            dispute: pda::dispute_address(market).0,
+            config: config(),
+            price_feed_config: pda::price_feed_config_address(price_feed).0,
+            price_feed: *price_feed,
+            challenger: *challenger,
+            system_program: system_program::ID,
+        },
+        instruction::ChallengeOutcome {},
+    )
+}
+
+pub fn settle_dispute(
+    arbiter: &Pubkey,
+    market: &Pubkey,
+    challenger: &Pubkey,
+    uphold: bool,
+) -> Instruction {
+    build(
+        accounts::SettleDispute {
+            market: *market,
+            dispute: pda::dispute_address(market).0,
+            arbiter_role: role(Role::Arbiter, arbiter),
+            arbiter: *arbiter,
+            challenger: *challenger,
+            team_wallet: TEAM_WALLET,
+        },
+        instruction::SettleDispute { uphold },
+    )
+}
+
+pub fn void_market(
+    authority: &Pubkey,
+    market: &Pubkey,
+    price_feed: &Pubkey,
+    reason: VoidReason,
+) -> Instruction {
+    build(
+        accounts::VoidMarket {
+            market: *market,
+            config: config(),
+            price_feed_config: pda::price_feed_config_address(price_feed).0,
+            admin_role: role(Role::Admin, authority),
+            authority: *authority,
+            team_wallet: TEAM_WALLET,
+        },
+        instruction::VoidMarket { reason },
+    )
+}
+
+pub fn refund_creator_bond(authority: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::RefundCreatorBond {
+            market: *market,
+            authority: *authority,
+        },
+        instruction::RefundCreatorBond {},
+    )
+}
+
+pub fn grant_role(
+    authority: &Pubkey,
+    proposal_id: u64,
+    granted: Role,
+    member: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::GrantRole {
+            role_assignment: role(granted, member),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::GrantRole {
+            role: granted,
+            member: *member,
+        },
+    )
+}
+
+pub fn revoke_role(
+    authority: &Pubkey,
+    proposal_id: u64,
+    revoked: Role,
+    member: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RevokeRole {
+            role_assignment: role(revoked, member),
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::RevokeRole {},
+    )
+}
+
+pub fn pause_program(guardian: &Pubkey, reason: PauseReason, allow_claims: bool) -> Instruction {
+    build(
+        accounts::PauseProgram {
+            config: config(),
+            guardian_role: role(Role::Guardian, guardian),
+            guardian: *guardian,
+        },
+        instruction::PauseProgram {
+            reason,
+            allow_claims,
+        },
+    )
+}
+
+pub fn resume_program(guardian: &Pubkey) -> Instruction {
+    build(
+        accounts::PauseProgram {
+            config: config(),
+            guardian_role: role(Role::Guardian, guardian),
+            guardian: *guardian,
+        },
+        instruction::ResumeProgram {},
+    )
+}
+
+pub fn pause_market(
+    guardian: &Pubkey,
+    market: &Pubkey,
+    reason: PauseReason,
+    allow_claims: bool,
+) -> Instruction {
+    build(
+        accounts::PauseMarket {
+            market: *market,
+            config: config(),
+            guardian_role: role(Role::Guardian, guardian),
+            guardian: *guardian,
+        },
+        instruction::PauseMarket {
+            reason,
+            allow_claims,
+        },
+    )
+}
+
+pub fn resume_market(guardian: &Pubkey, market: &Pubkey) -> Instruction {
+    build(
+        accounts::PauseMarket {
+            market: *market,
+            config: config(),
+            guardian_role: role(Role::Guardian, guardian),
+            guardian: *guardian,
+        },
+        instruction::ResumeMarket {},
+    )
+}
+
+/// `payer` must hold the admin role.
+pub fn initialize_price_feed(
+    payer: &Pubkey,
+    feed: &Pubkey,
+    params: PriceFeedParams,
+) -> Instruction {
+    build(
+        accounts::InitializePriceFeed {
+            admin_role: role(Role::Admin, payer),
+            price_feed_config: pda::price_feed_config_address(feed).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePriceFeed {
+            feed: *feed,
+            params,
+        },
+    )
+}
+
+pub fn update_price_feed(
+    authority: &Pubkey,
+    feed: &Pubkey,
+    proposal_id: u64,
+    params: PriceFeedParams,
+) -> Instruction {
+    build(
+        accounts::UpdatePriceFeed {
+            price_feed_config: pda::price_feed_config_address(feed).0,
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::UpdatePriceFeed { params },
+    )
+}
+
+pub fn enable_price_feed(authority: &Pubkey, feed: &Pubkey, proposal_id: u64) -> Instruction {
+    build(
+        accounts::UpdatePriceFeed {
+            price_feed_config: pda::price_feed_config_address(feed).0,
+            admin_role: role(Role::Admin, authority),
+            proposal: proposal(proposal_id),
+            authority: *authority,
+        },
+        instruction::EnablePriceFeed {},
+    )
+}
+
+pub fn disable_price_feed(authority: &Pubkey, feed: &Pubkey) -> Instruction {
+    build(
+        accounts::DisablePriceFeed {
+            price_feed_config: pda::price_feed_config_address(feed).0,
+            admin_role: role(Role::Admin, authority),
+            authority: *authority,
+        },
+        instruction::DisablePriceFeed {},
+    )
+}
+
+pub fn close_price_feed(authority: &Pubkey, feed: &Pubkey) -> Instruction {
+    build(
+        accounts::ClosePriceFeed {
+            price_feed_config: pda::price_feed_config_address(feed).0,
+            admin_role: role(Role::Admin, authority),
+            authority: *authority,
+        },
+        instruction::ClosePriceFeed {},
+    )
+}
@@ -0,0 +1,16 @@
+//! Rust client for the blink program: instruction builders, account
+//! decoding and error mapping.
+//!
+//! Addresses are derived with the program's own `pda` module and accounts
+//! are decoded into the program's own types, so the client cannot drift from
+//! the program it was built with.
+
+pub mod accounts;
+pub mod errors;
+pub mod instructions;
+
+pub use blink_core;
+pub use blink_take_2::errors::ErrorCode;
+pub use blink_take_2::{constants, pda, state, ID};
+
+pub use crate::errors::ClientError;
@@ -0,0 +1,1131 @@
+//! Runs the client's instructions against the program in a local bank.
+//!
+//! `initialize_config` can only be signed by the protocol authority, so the
+//! config, the roles and the price feed registration are written straight
+//! into the bank instead. Prices come from the mock oracle, which the
+//! program accepts when built with its `mock-oracle` feature.
+
+use anchor_lang::prelude::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{AccountSerialize, InstructionData, Space, ToAccountMetas};
+use blink_client::accounts::{decode, decode_market};
+use blink_client::constants::{ACCOUNT_VERSION_OFFSET, MARKET_CREATION_AUTHORITY};
+use blink_client::errors::program_error;
+use blink_client::instructions::{self, BetOptions};
+use blink_client::state::{
+    Config, Dispute, DisputePolicy, FeeSchedule, FeeTier, Market, MarketStatus, OracleSource,
+    PriceFeedConfig, ProposalAction, Role, RoleAssignment, UserPosition, UserProfile, VoidReason,
+};
+use blink_client::{pda, ErrorCode, ID};
+use mock_oracle::PRICE_ACCOUNT_SIZE;
+use solana_account_decoder::UiAccount;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+const NOW: i64 = 1_700_000_000;
+const MARKET_ID: u64 = 1;
+const DURATION: u64 = 3600;
+/// The first second markets created at `NOW` can be resolved.
+const EXPIRY: i64 = NOW + DURATION as i64 + 1;
+const INITIAL_PRICE: i64 = 1_000_000;
+/// As registered: prices expire after 60 seconds and the confidence
+/// interval may be at most 1% of the price.
+const MAX_STALENESS: u64 = 60;
+const MAX_CONFIDENCE_BPS: u16 = 100;
+const DISPUTE_WINDOW: i64 = 3600;
+const DISPUTE_BOND: u64 = 10_000_000;
+/// The only fee tier: half the fee back from 100M lamports of settled volume.
+const TIER_VOLUME: u64 = 100_000_000;
+/// The feed of the first release's market in `tests/fixtures`.
+const LEGACY_PRICE_FEED: Pubkey = pubkey!("62MyEqvd6t5EFAwH3pB29tG4Ey2w8Pd1PRthtUZuNVeq");
+/// Holds the position in `tests/fixtures` on that market.
+const LEGACY_USER: Pubkey = pubkey!("F5k2Qx5FRbEQT1cmnFWxRUVv8Rv9Km8ZyYExBgRFtLUW");
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // anchor's entry ties the slice to the lifetime of the accounts it holds
+    let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+    blink_take_2::entry(program_id, accounts, data)
+}
+
+fn process_oracle_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+    mock_oracle::entry(program_id, accounts, data)
+}
+
+fn program_account<T: AccountSerialize>(account: &T, space: usize) -> Account {
+    let mut data = Vec::with_capacity(space);
+    account.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn role_account(role: Role, member: &Pubkey) -> Account {
+    let assignment = RoleAssignment {
+        role,
+        member: *member,
+        granted_by: *member,
+        granted_at: NOW,
+    };
+    program_account(&assignment, 8 + RoleAssignment::INIT_SPACE)
+}
+
+/// Registers `price_feed` with a zeroed mock oracle price account, which
+/// `Env::set_price` fills in.
+fn add_price_feed(test: &mut ProgramTest, price_feed: Pubkey, symbol: &str) {
+    let (price_feed_config_address, bump) = pda::price_feed_config_address(&price_feed);
+    let price_feed_config = PriceFeedConfig {
+        version: PriceFeedConfig::VERSION,
+        price_feed,
+        symbol: symbol.to_string(),
+        decimals: 6,
+        oracle_source: OracleSource::Pyth,
+        max_staleness: MAX_STALENESS,
+        max_confidence_bps: MAX_CONFIDENCE_BPS,
+        enabled: true,
+        open_markets: 0,
+        bump,
+        reserved: [0; 23],
+    };
+    test.add_account(
+        price_feed_config_address,
+        program_account(&price_feed_config, 8 + PriceFeedConfig::INIT_SPACE),
+    );
+    test.add_account(
+        price_feed,
+        Account {
+            lamports: 1_000_000_000,
+            data: vec![0; PRICE_ACCOUNT_SIZE],
+            owner: mock_oracle::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// Adds an account written by the first release, from `tests/fixtures`, and
+/// returns its address.
+fn add_fixture(test: &mut ProgramTest, name: &str) -> Pubkey {
+    let path = format!(
+        "{}/../../tests/fixtures/{name}.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let fixture: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let address = fixture["pubkey"].as_str().unwrap().parse().unwrap();
+    let account: UiAccount = serde_json::from_value(fixture["account"].clone()).unwrap();
+    test.add_account(address, account.decode().unwrap());
+    address
+}
+
+struct Env {
+    context: ProgramTestContext,
+    admin: Keypair,
+    user: Keypair,
+    price_feed: Pubkey,
+    /// Registered like `price_feed`, but no market is created on it.
+    other_price_feed: Pubkey,
+    /// On `LEGACY_PRICE_FEED`, whose registration is loaded alongside it, as
+    /// is the position of `LEGACY_USER`.
+    legacy_market: Pubkey,
+}
+
+impl Env {
+    async fn start() -> Self {
+        let mut test = ProgramTest::new("blink_take_2", ID, processor!(process_instruction));
+        test.add_program(
+            "mock_oracle",
+            mock_oracle::ID,
+            processor!(process_oracle_instruction),
+        );
+        let admin = Keypair::new();
+        let user = Keypair::new();
+        let price_feed = Pubkey::new_unique();
+        let other_price_feed = Pubkey::new_unique();
+
+        for wallet in [&admin, &user] {
+            test.add_account(
+                wallet.pubkey(),
+                Account::new(100_000_000_000, 0, &solana_sdk::system_program::ID),
+            );
+        }
+
+        let config = Config {
+            authority: admin.pubkey(),
+            fee_schedule: FeeSchedule {
+                protocol_bps: 100,
+                creator_bps: 50,
+                referrer_bps: 25,
+                resolver_bps: 25,
+            },
+            creation_bond: 0,
+            fee_tiers: vec![FeeTier {
+                min_volume: TIER_VOLUME,
+                discount_bps: 5_000,
+            }],
+            governance_delay: 0,
+            exposure_limits: Default::default(),
+            dispute_policy: DisputePolicy {
+                window: DISPUTE_WINDOW,
+                bond: DISPUTE_BOND,
+            },
+            pause: Default::default(),
+        };
+        test.add_account(
+            pda::config_address().0,
+            program_account(&config, 8 + Config::INIT_SPACE),
+        );
+        test.add_account(
+            pda::role_address(Role::Admin, &admin.pubkey()).0,
+            role_account(Role::Admin, &admin.pubkey()),
+        );
+        test.add_account(
+            pda::role_address(Role::Resolver, &admin.pubkey()).0,
+            role_account(Role::Resolver, &admin.pubkey()),
+        );
+        test.add_account(
+            pda::role_address(Role::Arbiter, &admin.pubkey()).0,
+            role_account(Role::Arbiter, &admin.pubkey()),
+        );
+
+        add_price_feed(&mut test, price_feed, "BONK");
+        add_price_feed(&mut test, other_price_feed, "WIF");
+        add_fixture(&mut test, "price_feed_config_v0");
+        let legacy_market = add_fixture(&mut test, "market_v0");
+        add_fixture(&mut test, "user_position_v0");
+
+        let mut context = test.start_with_context().await;
+        let clock = Clock {
+            unix_timestamp: NOW,
+            ..context.banks_client.get_sysvar::<Clock>().await.unwrap()
+        };
+        context.set_sysvar(&clock);
+
+        let mut env = Env {
+            context,
+            admin,
+            user,
+            price_feed,
+            other_price_feed,
+            legacy_market,
+        };
+        env.set_price(INITIAL_PRICE, 1, NOW).await;
+        env.set_feed_price(other_price_feed, INITIAL_PRICE, 1, NOW)
+            .await;
+        env
+    }
+
+    /// Moves the clock past the end of markets created at `NOW` and
+    /// republishes the initial prices, which would otherwise be stale.
+    async fn expire(&mut self) {
+        self.warp(EXPIRY).await;
+        self.set_price(INITIAL_PRICE, 1, EXPIRY).await;
+        self.set_feed_price(self.other_price_feed, INITIAL_PRICE, 1, EXPIRY)
+            .await;
+    }
+
+    async fn warp(&mut self, unix_timestamp: i64) {
+        let clock = Clock {
+            unix_timestamp,
+            ..self
+                .context
+                .banks_client
+                .get_sysvar::<Clock>()
+                .await
+                .unwrap()
+        };
+        self.context.set_sysvar(&clock);
+    }
+
+    async fn set_price(&mut self, price: i64, conf: u64, publish_time: i64) {
+        self.set_feed_price(self.price_feed, price, conf, publish_time)
+            .await;
+    }
+
+    async fn set_feed_price(
+        &mut self,
+        price_feed: Pubkey,
+        price: i64,
+        conf: u64,
+        publish_time: i64,
+    ) {
+        let ix = Instruction {
+            program_id: mock_oracle::ID,
+            accounts: mock_oracle::accounts::SetPrice {
+                price_account: price_feed,
+            }
+            .to_account_metas(None),
+            data: mock_oracle::instruction::SetPrice {
+                price,
+                conf,
+                expo: -6,
+                publish_time,
+            }
+            .data(),
+        };
+        let payer = self.admin.insecure_clone();
+        self.send(ix, &payer).await.unwrap();
+    }
+
+    async fn resolve(&mut self, market: Pubkey) -> Result<(), TransactionError> {
+        let admin = self.admin.insecure_clone();
+        let ix = instructions::resolve_market(&admin.pubkey(), &market, &self.price_feed);
+        self.send(ix, &admin).await
+    }
+
+    async fn send(
+        &mut self,
+        instruction: Instruction,
+        signer: &Keypair,
+    ) -> Result<(), TransactionError> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|error| error.unwrap())
+    }
+
+    async fn data(&mut self, address: Pubkey) -> Vec<u8> {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .expect("account exists")
+            .data
+    }
+
+    async fn create_market(&mut self) -> Pubkey {
+        let authority = self.admin.insecure_clone();
+        let ix = instructions::create_market(
+            &authority.pubkey(),
+            MARKET_ID,
+            DURATION,
+            &self.price_feed,
+            false,
+        );
+        self.send(ix, &authority).await.unwrap();
+        pda::market_address(&authority.pubkey(), MARKET_ID).0
+    }
+}
+
+#[tokio::test]
+async fn create_market_and_place_bet() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let user = env.user.insecure_clone();
+
+    env.send(instructions::create_user(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+    let bet = instructions::place_bet(
+        &user.pubkey(),
+        &market,
+        50_000_000,
+        true,
+        BetOptions {
+            receipt_sequence: Some(0),
+            ..BetOptions::default()
+        },
+    );
+    env.send(bet, &user).await.unwrap();
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.status(), MarketStatus::Open);
+    assert_eq!(state.authority, env.admin.pubkey());
+    assert_eq!(
+        state.bump,
+        pda::market_address(&env.admin.pubkey(), MARKET_ID).1
+    );
+    assert_eq!(state.initial_price, INITIAL_PRICE);
+    assert_eq!(state.total_yes_shares, 50_000_000);
+
+    let (position_address, bump) = pda::user_position_address(&market, &user.pubkey());
+    let position: UserPosition = decode(&env.data(position_address).await).unwrap();
+    assert_eq!(position.yes_shares, 50_000_000);
+    assert_eq!(position.bet_count, 1);
+    assert_eq!(position.bump, bump);
+}
+
+#[tokio::test]
+async fn resolving_before_expiry_is_rejected() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+
+    let error = env.resolve(market).await.unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::MarketNotExpired)
+    ));
+
+    env.expire().await;
+    env.resolve(market).await.unwrap();
+}
+
+#[tokio::test]
+async fn higher_price_resolves_yes() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+
+    env.set_price(1_500_000, 1, EXPIRY).await;
+    env.resolve(market).await.unwrap();
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.status(), MarketStatus::Resolved);
+    assert_eq!(state.winning_outcome(), Some(true));
+    assert_eq!(state.final_price, 1_500_000);
+    assert_eq!(state.resolver(), Some(env.admin.pubkey()));
+}
+
+#[tokio::test]
+async fn lower_price_resolves_no() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+
+    env.set_price(500_000, 1, EXPIRY).await;
+    env.resolve(market).await.unwrap();
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.winning_outcome(), Some(false));
+}
+
+#[tokio::test]
+async fn unchanged_price_resolves_no() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+
+    env.resolve(market).await.unwrap();
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.final_price, INITIAL_PRICE);
+    assert_eq!(state.winning_outcome(), Some(false));
+}
+
+#[tokio::test]
+async fn stale_price_is_rejected() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+
+    env.set_price(1_500_000, 1, EXPIRY - MAX_STALENESS as i64 - 1)
+        .await;
+    let error = env.resolve(market).await.unwrap_err();
+
+    assert!(matches!(program_error(&error), Some(ErrorCode::StalePrice)));
+}
+
+#[tokio::test]
+async fn wide_confidence_is_rejected() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+
+    let conf = 1_500_000 * MAX_CONFIDENCE_BPS as u64 / 10_000 + 1;
+    env.set_price(1_500_000, conf, EXPIRY).await;
+    let error = env.resolve(market).await.unwrap_err();
+
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::PriceConfidenceTooWide)
+    ));
+}
+
+#[tokio::test]
+async fn resolving_against_another_registered_feed_is_rejected() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+
+    env.set_feed_price(env.other_price_feed, 1_500_000, 1, EXPIRY)
+        .await;
+    let admin = env.admin.insecure_clone();
+    let ix = instructions::resolve_market(&admin.pubkey(), &market, &env.other_price_feed);
+    let error = env.send(ix, &admin).await.unwrap_err();
+
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::PriceFeedMismatch)
+    ));
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.status(), MarketStatus::Open);
+}
+
+#[tokio::test]
+async fn price_feed_closes_once_its_markets_are_settled() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+    let admin = env.admin.insecure_clone();
+    let price_feed_config = pda::price_feed_config_address(&env.price_feed).0;
+
+    let config: PriceFeedConfig = decode(&env.data(price_feed_config).await).unwrap();
+    assert_eq!(config.open_markets, 1);
+
+    env.send(
+        instructions::disable_price_feed(&admin.pubkey(), &env.price_feed),
+        &admin,
+    )
+    .await
+    .unwrap();
+    let close = instructions::close_price_feed(&admin.pubkey(), &env.price_feed);
+    let error = env.send(close.clone(), &admin).await.unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::PriceFeedInUse)
+    ));
+
+    env.resolve(market).await.unwrap();
+    env.send(close, &admin).await.unwrap();
+
+    let account = env
+        .context
+        .banks_client
+        .get_account(price_feed_config)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+}
+
+#[tokio::test]
+async fn enabling_a_feed_goes_through_a_proposal() {
+    let mut env = Env::start().await;
+    let admin = env.admin.insecure_clone();
+    let price_feed = env.price_feed;
+
+    env.send(
+        instructions::disable_price_feed(&admin.pubkey(), &price_feed),
+        &admin,
+    )
+    .await
+    .unwrap();
+
+    let other = ProposalAction::EnablePriceFeed {
+        price_feed: env.other_price_feed,
+    };
+    env.send(
+        instructions::queue_proposal(&admin.pubkey(), 1, other),
+        &admin,
+    )
+    .await
+    .unwrap();
+    let error = env
+        .send(
+            instructions::enable_price_feed(&admin.pubkey(), &price_feed, 1),
+            &admin,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::ProposalActionMismatch)
+    ));
+
+    let action = ProposalAction::EnablePriceFeed { price_feed };
+    env.send(
+        instructions::queue_proposal(&admin.pubkey(), 2, action),
+        &admin,
+    )
+    .await
+    .unwrap();
+    env.send(
+        instructions::enable_price_feed(&admin.pubkey(), &price_feed, 2),
+        &admin,
+    )
+    .await
+    .unwrap();
+    let address = pda::price_feed_config_address(&price_feed).0;
+    let config: PriceFeedConfig = decode(&env.data(address).await).unwrap();
+    assert!(config.enabled);
+
+    // the first release kept no symbol, so its feeds stay disabled until one
+    // is set
+    env.send(
+        instructions::migrate_price_feed(&admin.pubkey(), &LEGACY_PRICE_FEED),
+        &admin,
+    )
+    .await
+    .unwrap();
+    let action = ProposalAction::EnablePriceFeed {
+        price_feed: LEGACY_PRICE_FEED,
+    };
+    env.send(
+        instructions::queue_proposal(&admin.pubkey(), 3, action),
+        &admin,
+    )
+    .await
+    .unwrap();
+    let error = env
+        .send(
+            instructions::enable_price_feed(&admin.pubkey(), &LEGACY_PRICE_FEED, 3),
+            &admin,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::InvalidSymbol)
+    ));
+}
+
+#[tokio::test]
+async fn challenge_reads_the_market_feed() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    env.expire().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user.insecure_clone();
+
+    env.set_price(500_000, 1, EXPIRY).await;
+    env.resolve(market).await.unwrap();
+
+    // the feed still agrees with the resolution
+    let challenge = instructions::challenge_outcome(&user.pubkey(), &market, &env.price_feed);
+    let error = env.send(challenge.clone(), &user).await.unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::DisputeOutcomeUnchanged)
+    ));
+
+    env.set_feed_price(env.other_price_feed, 1_500_000, 1, EXPIRY)
+        .await;
+    let other_feed =
+        instructions::challenge_outcome(&user.pubkey(), &market, &env.other_price_feed);
+    let error = env.send(other_feed, &user).await.unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::PriceFeedMismatch)
+    ));
+
+    env.set_price(1_500_000, 1, EXPIRY - MAX_STALENESS as i64 - 1)
+        .await;
+    let error = env.send(challenge.clone(), &user).await.unwrap_err();
+    assert!(matches!(program_error(&error), Some(ErrorCode::StalePrice)));
+
+    env.set_price(1_500_000, 1, EXPIRY).await;
+    env.send(challenge, &user).await.unwrap();
+
+    let dispute: Dispute = decode(&env.data(pda::dispute_address(&market).0).await).unwrap();
+    assert_eq!(dispute.claimed_price, 1_500_000);
+    assert!(dispute.proposed_outcome);
+    assert_eq!(dispute.bond, DISPUTE_BOND);
+
+    let settle = instructions::settle_dispute(&admin.pubkey(), &market, &user.pubkey(), true);
+    env.send(settle, &admin).await.unwrap();
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.winning_outcome(), Some(true));
+    assert_eq!(state.final_price, 1_500_000);
+}
+
+#[tokio::test]
+async fn voided_market_refunds_the_stake() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user.insecure_clone();
+
+    env.send(instructions::create_user(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+    let bet = instructions::place_bet(
+        &user.pubkey(),
+        &market,
+        50_000_000,
+        true,
+        BetOptions::default(),
+    );
+    env.send(bet, &user).await.unwrap();
+    let void = instructions::void_market(
+        &admin.pubkey(),
+        &market,
+        &env.price_feed,
+        VoidReason::OracleFailure,
+    );
+    env.send(void, &admin).await.unwrap();
+    env.send(instructions::claim_winnings(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+
+    let position_address = pda::user_position_address(&market, &user.pubkey()).0;
+    let position: UserPosition = decode(&env.data(position_address).await).unwrap();
+    assert!(position.claimed);
+    assert_eq!(position.claimed_amount, 50_000_000);
+    assert_eq!(position.yes_shares, 0);
+    assert_eq!(position.cost_basis(), 0);
+}
+
+#[tokio::test]
+async fn cancellation_penalties_are_paid_to_the_winners() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user.insecure_clone();
+
+    for (wallet, choice) in [(&user, true), (&admin, false)] {
+        env.send(instructions::create_user(&wallet.pubkey(), &market), wallet)
+            .await
+            .unwrap();
+        let bet = instructions::place_bet(
+            &wallet.pubkey(),
+            &market,
+            50_000_000,
+            choice,
+            BetOptions::default(),
+        );
+        env.send(bet, wallet).await.unwrap();
+    }
+
+    // halfway through, only part of the stake is refunded
+    env.warp(NOW + DURATION as i64 / 2).await;
+    env.send(
+        instructions::cancel_bet(&admin.pubkey(), &market, false),
+        &admin,
+    )
+    .await
+    .unwrap();
+    let admin_position = pda::user_position_address(&market, &admin.pubkey()).0;
+    let position: UserPosition = decode(&env.data(admin_position).await).unwrap();
+    let penalty = 50_000_000 - position.refunded;
+    assert!(penalty > 0);
+
+    env.expire().await;
+    env.set_price(1_500_000, 1, EXPIRY).await;
+    env.resolve(market).await.unwrap();
+    env.warp(EXPIRY + DISPUTE_WINDOW).await;
+    env.send(instructions::claim_winnings(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.cancellation_penalties, penalty);
+    assert_eq!(state.total_pool(), 50_000_000 + penalty);
+    let user_position = pda::user_position_address(&market, &user.pubkey()).0;
+    let position: UserPosition = decode(&env.data(user_position).await).unwrap();
+    assert_eq!(
+        position.claimed_amount,
+        state.total_pool() - state.fees().total()
+    );
+}
+
+#[tokio::test]
+async fn settled_positions_cannot_be_cancelled() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user.insecure_clone();
+
+    for (wallet, choice) in [(&user, true), (&admin, false)] {
+        env.send(instructions::create_user(&wallet.pubkey(), &market), wallet)
+            .await
+            .unwrap();
+        let bet = instructions::place_bet(
+            &wallet.pubkey(),
+            &market,
+            50_000_000,
+            choice,
+            BetOptions::default(),
+        );
+        env.send(bet, wallet).await.unwrap();
+    }
+
+    env.expire().await;
+    env.set_price(1_500_000, 1, EXPIRY).await;
+    env.resolve(market).await.unwrap();
+    env.warp(EXPIRY + DISPUTE_WINDOW).await;
+
+    // the winner is paid once, and the loser cannot take the stake back
+    env.send(instructions::claim_winnings(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+    for wallet in [&user, &admin] {
+        let cancel = instructions::cancel_bet(&wallet.pubkey(), &market, false);
+        let error = env.send(cancel, wallet).await.unwrap_err();
+        assert!(matches!(
+            program_error(&error),
+            Some(ErrorCode::MarketAlreadyResolved)
+        ));
+    }
+
+    let position_address = pda::user_position_address(&market, &user.pubkey()).0;
+    let position: UserPosition = decode(&env.data(position_address).await).unwrap();
+    assert_eq!(position.refunded, 0);
+    assert_eq!(position.cost_basis(), 0);
+}
+
+#[tokio::test]
+async fn claim_and_cancel_create_a_missing_profile() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user.insecure_clone();
+
+    // create_user opens a position without a profile
+    for wallet in [&admin, &user] {
+        env.send(instructions::create_user(&wallet.pubkey(), &market), wallet)
+            .await
+            .unwrap();
+    }
+
+    env.send(
+        instructions::cancel_bet(&user.pubkey(), &market, false),
+        &user,
+    )
+    .await
+    .unwrap();
+    let void = instructions::void_market(
+        &admin.pubkey(),
+        &market,
+        &env.price_feed,
+        VoidReason::OracleFailure,
+    );
+    env.send(void, &admin).await.unwrap();
+    env.send(
+        instructions::claim_winnings(&admin.pubkey(), &market),
+        &admin,
+    )
+    .await
+    .unwrap();
+
+    for wallet in [&admin, &user] {
+        let address = pda::user_profile_address(&wallet.pubkey()).0;
+        let profile: UserProfile = decode(&env.data(address).await).unwrap();
+        assert_eq!(profile.user, wallet.pubkey());
+        assert_eq!(profile.realized_pnl, 0);
+    }
+}
+
+#[tokio::test]
+async fn cancelled_bets_do_not_count_as_volume() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let user = env.user.insecure_clone();
+    let profile_address = pda::user_profile_address(&user.pubkey()).0;
+
+    env.send(instructions::create_user(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+    for amount in [50_000_000, 20_000_000] {
+        let bet =
+            instructions::place_bet(&user.pubkey(), &market, amount, true, BetOptions::default());
+        env.send(bet, &user).await.unwrap();
+    }
+    env.send(
+        instructions::cancel_bet(&user.pubkey(), &market, false),
+        &user,
+    )
+    .await
+    .unwrap();
+
+    let profile: UserProfile = decode(&env.data(profile_address).await).unwrap();
+    assert_eq!(profile.total_volume, 0);
+    assert_eq!(profile.markets_entered, 0);
+
+    // betting again enters the market again
+    let bet = instructions::place_bet(
+        &user.pubkey(),
+        &market,
+        30_000_000,
+        false,
+        BetOptions::default(),
+    );
+    env.send(bet, &user).await.unwrap();
+    let profile: UserProfile = decode(&env.data(profile_address).await).unwrap();
+    assert_eq!(profile.total_volume, 30_000_000);
+    assert_eq!(profile.markets_entered, 1);
+}
+
+#[tokio::test]
+async fn fee_tiers_count_only_settled_volume() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user.insecure_clone();
+
+    // volume on a market still open for cancellation
+    let open_market = pda::market_address(&admin.pubkey(), MARKET_ID + 1).0;
+    let create = instructions::create_market(
+        &admin.pubkey(),
+        MARKET_ID + 1,
+        DURATION,
+        &env.price_feed,
+        false,
+    );
+    env.send(create, &admin).await.unwrap();
+    for (market, amount) in [(market, 50_000_000), (open_market, TIER_VOLUME)] {
+        env.send(instructions::create_user(&user.pubkey(), &market), &user)
+            .await
+            .unwrap();
+        let bet =
+            instructions::place_bet(&user.pubkey(), &market, amount, true, BetOptions::default());
+        env.send(bet, &user).await.unwrap();
+    }
+
+    // the admin's position ends up without a stake, leaving a penalty for the
+    // user to win
+    env.send(instructions::create_user(&admin.pubkey(), &market), &admin)
+        .await
+        .unwrap();
+    let bet = instructions::place_bet(
+        &admin.pubkey(),
+        &market,
+        50_000_000,
+        false,
+        BetOptions::default(),
+    );
+    env.send(bet, &admin).await.unwrap();
+    env.warp(NOW + DURATION as i64 / 2).await;
+    env.send(
+        instructions::cancel_bet(&admin.pubkey(), &market, false),
+        &admin,
+    )
+    .await
+    .unwrap();
+
+    env.expire().await;
+    env.set_price(1_500_000, 1, EXPIRY).await;
+    env.resolve(market).await.unwrap();
+    env.warp(EXPIRY + DISPUTE_WINDOW).await;
+    for wallet in [&user, &admin] {
+        env.send(
+            instructions::claim_winnings(&wallet.pubkey(), &market),
+            wallet,
+        )
+        .await
+        .unwrap();
+    }
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.fee_rebates, 0);
+    let position_address = pda::user_position_address(&market, &user.pubkey()).0;
+    let position: UserPosition = decode(&env.data(position_address).await).unwrap();
+    assert_eq!(
+        position.claimed_amount,
+        state.total_pool() - state.fees().total()
+    );
+
+    let profile_address = pda::user_profile_address(&user.pubkey()).0;
+    let profile: UserProfile = decode(&env.data(profile_address).await).unwrap();
+    assert_eq!(profile.total_volume, 50_000_000 + TIER_VOLUME);
+    assert_eq!(profile.settled_volume, 50_000_000);
+    assert_eq!(profile.wins, 1);
+
+    let profile_address = pda::user_profile_address(&admin.pubkey()).0;
+    let profile: UserProfile = decode(&env.data(profile_address).await).unwrap();
+    assert_eq!(profile.settled_volume, 0);
+    assert_eq!((profile.wins, profile.losses), (0, 0));
+}
+
+#[tokio::test]
+async fn first_release_market_migrates() {
+    let mut env = Env::start().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user.insecure_clone();
+    let market = env.legacy_market;
+
+    env.send(
+        instructions::migrate_price_feed(&admin.pubkey(), &LEGACY_PRICE_FEED),
+        &admin,
+    )
+    .await
+    .unwrap();
+    env.send(
+        instructions::migrate_market(&admin.pubkey(), &market, &LEGACY_PRICE_FEED),
+        &admin,
+    )
+    .await
+    .unwrap();
+
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.version, Market::VERSION);
+    assert_eq!(state.status(), MarketStatus::Open);
+    assert_eq!(state.symbol(), "BONK");
+    assert_eq!(state.feed_id, LEGACY_PRICE_FEED);
+    assert_eq!(
+        state.bump,
+        pda::symbol_market_address(&MARKET_CREATION_AUTHORITY, "BONK").1
+    );
+    assert_eq!(state.total_yes_shares, 500_000_000);
+    assert_eq!(state.total_no_shares, 300_000_000);
+    assert_eq!(state.fee_schedule.protocol_bps, 500);
+    let config_address = pda::price_feed_config_address(&LEGACY_PRICE_FEED).0;
+    let config: PriceFeedConfig = decode(&env.data(config_address).await).unwrap();
+    assert_eq!(config.open_markets, 1);
+
+    // bets still find the market at its symbol-seeded address
+    env.send(instructions::create_user(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+    let bet = instructions::place_bet(
+        &user.pubkey(),
+        &market,
+        50_000_000,
+        true,
+        BetOptions::default(),
+    );
+    env.send(bet, &user).await.unwrap();
+    let state = decode_market(&env.data(market).await).unwrap();
+    assert_eq!(state.total_yes_shares, 550_000_000);
+
+    let error = env
+        .send(
+            instructions::migrate_market(&admin.pubkey(), &market, &LEGACY_PRICE_FEED),
+            &admin,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::AccountAlreadyMigrated)
+    ));
+}
+
+#[tokio::test]
+async fn first_release_position_migrates() {
+    let mut env = Env::start().await;
+    let admin = env.admin.insecure_clone();
+    let market = env.legacy_market;
+
+    env.send(
+        instructions::migrate_position(&admin.pubkey(), &market, &LEGACY_USER),
+        &admin,
+    )
+    .await
+    .unwrap();
+
+    let (position_address, bump) = pda::user_position_address(&market, &LEGACY_USER);
+    let data = env.data(position_address).await;
+    assert_eq!(data.len(), 8 + UserPosition::INIT_SPACE);
+    let position: UserPosition = decode(&data).unwrap();
+    assert_eq!(position.version, UserPosition::VERSION);
+    assert_eq!(position.market, market);
+    assert_eq!(position.user, LEGACY_USER);
+    assert_eq!(position.bump, bump);
+    assert_eq!(position.yes_shares, 200_000_000);
+    assert_eq!(position.no_shares, 100_000_000);
+    assert!(!position.claimed);
+    assert!(!position.referred);
+    assert_eq!(position.bet_count, 0);
+    assert_eq!(position.deposited_yes, 200_000_000);
+    assert_eq!(position.deposited_no, 100_000_000);
+
+    let error = env
+        .send(
+            instructions::migrate_position(&admin.pubkey(), &market, &LEGACY_USER),
+            &admin,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::AccountAlreadyMigrated)
+    ));
+}
+
+#[tokio::test]
+async fn unreleased_account_versions_are_rejected() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let user = env.user.insecure_clone();
+    let admin = env.admin.insecure_clone();
+
+    env.send(instructions::create_user(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+    let (position_address, _) = pda::user_position_address(&market, &user.pubkey());
+    let mut position = env
+        .context
+        .banks_client
+        .get_account(position_address)
+        .await
+        .unwrap()
+        .unwrap();
+    position.data[ACCOUNT_VERSION_OFFSET] = UserPosition::VERSION - 1;
+    env.context.set_account(&position_address, &position.into());
+
+    let error = env
+        .send(
+            instructions::migrate_position(&admin.pubkey(), &market, &user.pubkey()),
+            &admin,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::UnsupportedAccountVersion)
+    ));
+
+    // too short to hold a version, which must not be read past its end
+    let mut account = env
+        .context
+        .banks_client
+        .get_account(market)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data.truncate(8);
+    env.context.set_account(&market, &account.into());
+
+    let error = env
+        .send(
+            instructions::migrate_market(&admin.pubkey(), &market, &env.price_feed),
+            &admin,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::UnsupportedAccountVersion)
+    ));
+}
+
+#[tokio::test]
+async fn program_errors_map_to_error_codes() {
+    let mut env = Env::start().await;
+    let market = env.create_market().await;
+    let user = env.user.insecure_clone();
+
+    env.send(instructions::create_user(&user.pubkey(), &market), &user)
+        .await
+        .unwrap();
+    let error = env
+        .send(
+            instructions::place_bet(&user.pubkey(), &market, 1, true, BetOptions::default()),
+            &user,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        program_error(&error),
+        Some(ErrorCode::BetAmountTooLow)
+    ));
+}
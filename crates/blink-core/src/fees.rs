@@ -0,0 +1,134 @@
+//! Fee splitting. Fees are charged on a market's whole pool at settlement and
+//! withheld from the winners' payouts.
+
+use crate::bps_of;
+
+/// The fee rates of a market, in basis points of its pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeRates {
+    pub protocol_bps: u16,
+    pub creator_bps: u16,
+    pub referrer_bps: u16,
+    pub resolver_bps: u16,
+}
+
+impl FeeRates {
+    pub fn total_bps(&self) -> u64 {
+        self.protocol_bps as u64
+            + self.creator_bps as u64
+            + self.referrer_bps as u64
+            + self.resolver_bps as u64
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    pub protocol: u64,
+    pub creator: u64,
+    pub referrer: u64,
+    pub resolver: u64,
+}
+
+impl FeeBreakdown {
+    pub fn total(&self) -> u64 {
+        self.protocol + self.creator + self.referrer + self.resolver
+    }
+}
+
+/// Splits the fee owed on `pool` between every recipient in `rates`.
+///
+/// Only `referred_volume` earns the referrer share; the share on the rest of the
+/// pool is routed to the protocol.
+pub fn calculate_fees(pool: u64, referred_volume: u64, rates: &FeeRates) -> FeeBreakdown {
+    let unreferred_volume = pool.saturating_sub(referred_volume);
+
+    FeeBreakdown {
+        protocol: bps_of(pool, rates.protocol_bps) + bps_of(unreferred_volume, rates.referrer_bps),
+        creator: bps_of(pool, rates.creator_bps),
+        referrer: bps_of(pool - unreferred_volume, rates.referrer_bps),
+        resolver: bps_of(pool, rates.resolver_bps),
+    }
+}
+
+/// Returns the discount of the highest tier reached by `volume`, or 0 when no
+/// tier applies. `tiers` are `(min_volume, discount_bps)` pairs sorted by
+/// increasing `min_volume`.
+pub fn resolve_fee_discount_bps(volume: u64, tiers: impl IntoIterator<Item = (u64, u16)>) -> u16 {
+    tiers
+        .into_iter()
+        .take_while(|&(min_volume, _)| min_volume <= volume)
+        .last()
+        .map_or(0, |(_, discount_bps)| discount_bps)
+}
+
+pub fn calculate_fee_rebate(fee: u64, discount_bps: u16) -> u64 {
+    bps_of(fee, discount_bps)
+}
+
+pub fn calculate_referral_reward(referred_volume: u64, referrer_bps: u16) -> u64 {
+    bps_of(referred_volume, referrer_bps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATES: FeeRates = FeeRates {
+        protocol_bps: 100,
+        creator_bps: 50,
+        referrer_bps: 25,
+        resolver_bps: 25,
+    };
+
+    #[test]
+    fn unreferred_pool_routes_referrer_share_to_protocol() {
+        let fees = calculate_fees(1_000_000, 0, &RATES);
+
+        assert_eq!(fees.protocol, 12_500);
+        assert_eq!(fees.creator, 5_000);
+        assert_eq!(fees.referrer, 0);
+        assert_eq!(fees.resolver, 2_500);
+        assert_eq!(fees.total(), 20_000);
+    }
+
+    #[test]
+    fn referred_volume_earns_referrer_share() {
+        let fees = calculate_fees(1_000_000, 400_000, &RATES);
+
+        assert_eq!(fees.referrer, 1_000);
+        assert_eq!(fees.protocol, 10_000 + 1_500);
+    }
+
+    #[test]
+    fn referred_volume_above_pool_is_capped() {
+        assert_eq!(
+            calculate_fees(1_000_000, 2_000_000, &RATES),
+            calculate_fees(1_000_000, 1_000_000, &RATES)
+        );
+    }
+
+    #[test]
+    fn empty_pool_owes_no_fees() {
+        assert_eq!(calculate_fees(0, 0, &RATES), FeeBreakdown::default());
+    }
+
+    #[test]
+    fn discount_of_highest_reached_tier() {
+        let tiers = [(1_000, 100), (10_000, 500), (100_000, 1_000)];
+
+        assert_eq!(resolve_fee_discount_bps(999, tiers), 0);
+        assert_eq!(resolve_fee_discount_bps(1_000, tiers), 100);
+        assert_eq!(resolve_fee_discount_bps(50_000, tiers), 500);
+        assert_eq!(resolve_fee_discount_bps(u64::MAX, tiers), 1_000);
+        assert_eq!(resolve_fee_discount_bps(u64::MAX, []), 0);
+    }
+
+    #[test]
+    fn rebate_and_referral_reward() {
+        assert_eq!(calculate_fee_rebate(20_000, 5_000), 10_000);
+        assert_eq!(
+            calculate_referral_reward(400_000, RATES.referrer_bps),
+            1_000
+        );
+    }
+}
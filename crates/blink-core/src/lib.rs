@@ -0,0 +1,32 @@
+//! Market math shared by the on-chain program and off-chain tools: the market
+//! status machine, fee splitting, payouts, cancellation refunds and pricing.
+//!
+//! Everything here is pure and `no_std`, so the program and the services
+//! quoting its markets compute the same numbers.
+
+#![cfg_attr(not(test), no_std)]
+
+pub mod fees;
+pub mod payout;
+pub mod pricing;
+pub mod refund;
+pub mod state;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// `bps` basis points of `amount`, rounded down.
+pub fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(10_000, 1), 1);
+        assert_eq!(bps_of(9_999, 1), 0);
+        assert_eq!(bps_of(u64::MAX, 10_000), u64::MAX);
+    }
+}
@@ -0,0 +1,63 @@
+//! Winner payouts. The winning side splits the pool pro rata to its shares,
+//! net of the market's fees.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Payout {
+    /// Lamports owed to the bettor.
+    pub amount: u64,
+    /// Fees withheld from the bettor's gross share of the pool.
+    pub fee: u64,
+}
+
+/// Returns the payout of `shares` on the winning side, which holds
+/// `winning_side_shares` of a market whose pool is `pool` and owes `fees`.
+///
+/// Nothing is owed when the winning side is empty. `shares` must not exceed
+/// `winning_side_shares`.
+pub fn calculate_payout(shares: u64, winning_side_shares: u64, pool: u64, fees: u64) -> Payout {
+    if winning_side_shares == 0 {
+        return Payout::default();
+    }
+
+    let winnings_pool = pool.saturating_sub(fees);
+    let gross = (shares as u128 * pool as u128 / winning_side_shares as u128) as u64;
+    let amount = (shares as u128 * winnings_pool as u128 / winning_side_shares as u128) as u64;
+
+    Payout {
+        amount,
+        fee: gross - amount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sole_winner_takes_pool_net_of_fees() {
+        let payout = calculate_payout(500, 500, 1_500, 30);
+
+        assert_eq!(payout.amount, 1_470);
+        assert_eq!(payout.fee, 30);
+    }
+
+    #[test]
+    fn winners_split_pro_rata() {
+        let first = calculate_payout(300, 400, 1_000, 20);
+        let second = calculate_payout(100, 400, 1_000, 20);
+
+        assert_eq!(first.amount, 735);
+        assert_eq!(second.amount, 245);
+        assert_eq!(first.fee + second.fee, 20);
+    }
+
+    #[test]
+    fn empty_winning_side_pays_nothing() {
+        assert_eq!(calculate_payout(0, 0, 1_000, 20), Payout::default());
+    }
+
+    #[test]
+    fn losing_position_pays_nothing() {
+        assert_eq!(calculate_payout(0, 400, 1_000, 20), Payout::default());
+    }
+}
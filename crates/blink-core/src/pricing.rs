@@ -0,0 +1,45 @@
+//! Share pricing and oracle price checks.
+
+use crate::BPS_DENOMINATOR;
+
+/// Shares are minted one per lamport bet.
+pub fn calculate_shares(amount: u64) -> u64 {
+    amount
+}
+
+/// Whether an oracle's confidence interval `conf` around `price` is within
+/// `max_confidence_bps` of the price.
+pub fn confidence_within(price: i64, conf: u64, max_confidence_bps: u16) -> bool {
+    let max_conf = price.unsigned_abs() as u128 * max_confidence_bps as u128;
+    conf as u128 * BPS_DENOMINATOR as u128 <= max_conf
+}
+
+/// Yes wins only if the price ended strictly above where it started.
+pub fn winning_outcome(initial_price: i64, final_price: i64) -> bool {
+    final_price > initial_price
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_are_one_per_lamport() {
+        assert_eq!(calculate_shares(1_000_000), 1_000_000);
+    }
+
+    #[test]
+    fn confidence_bound_is_inclusive() {
+        assert!(confidence_within(10_000, 100, 100));
+        assert!(!confidence_within(10_000, 101, 100));
+        assert!(confidence_within(-10_000, 100, 100));
+        assert!(!confidence_within(0, 1, 10_000));
+    }
+
+    #[test]
+    fn flat_price_resolves_no() {
+        assert!(winning_outcome(100, 101));
+        assert!(!winning_outcome(100, 100));
+        assert!(!winning_outcome(100, 99));
+    }
+}
@@ -0,0 +1,55 @@
+//! Cancellation refunds. Bettors can back out early in a market, and the
+//! refund shrinks the longer the market has been running.
+
+/// Bets can only be cancelled this long after the market starts.
+pub const CANCELLATION_WINDOW: u64 = 6 * 60 * 60;
+
+pub fn can_cancel(elapsed_time: u64) -> bool {
+    elapsed_time <= CANCELLATION_WINDOW
+}
+
+/// Refunds `original_amount` scaled by the remaining fraction of the market's
+/// duration raised to the power 1.5, so nothing is refunded once it has ended.
+pub fn calculate_refund_amount(
+    original_amount: u64,
+    elapsed_time: u64,
+    total_duration: u64,
+) -> u64 {
+    if elapsed_time >= total_duration {
+        return 0;
+    }
+
+    let time_ratio = (total_duration - elapsed_time) as f64 / total_duration as f64;
+    let refund_ratio = libm::pow(time_ratio, 1.5);
+
+    (original_amount as f64 * refund_ratio) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelling_immediately_refunds_in_full() {
+        assert_eq!(calculate_refund_amount(1_000_000, 0, 3_600), 1_000_000);
+    }
+
+    #[test]
+    fn refund_follows_remaining_time() {
+        // a quarter of the duration left refunds (1/4)^1.5 = 1/8
+        assert_eq!(calculate_refund_amount(800_000, 2_700, 3_600), 100_000);
+    }
+
+    #[test]
+    fn nothing_is_refunded_after_the_market_ends() {
+        assert_eq!(calculate_refund_amount(1_000_000, 3_600, 3_600), 0);
+        assert_eq!(calculate_refund_amount(1_000_000, 7_200, 3_600), 0);
+        assert_eq!(calculate_refund_amount(1_000_000, 0, 0), 0);
+    }
+
+    #[test]
+    fn cancellation_window_is_inclusive() {
+        assert!(can_cancel(CANCELLATION_WINDOW));
+        assert!(!can_cancel(CANCELLATION_WINDOW + 1));
+    }
+}
@@ -0,0 +1,212 @@
+//! The market lifecycle. A market opens for bets, then either resolves to an
+//! outcome or is voided and refunds everyone; both are final. A resolution can
+//! still be challenged until its dispute deadline.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MarketStatus {
+    Open,
+    Resolved,
+    Voided,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketError {
+    NotActive,
+    NotResolved,
+    AlreadyResolved,
+    Voided,
+    Disputed,
+    DisputeWindowOpen,
+}
+
+impl MarketStatus {
+    /// Decodes the status byte stored on a market. Unknown values read as
+    /// voided, the state that moves no funds but refunds.
+    pub fn from_u8(status: u8) -> Self {
+        match status {
+            0 => Self::Open,
+            1 => Self::Resolved,
+            _ => Self::Voided,
+        }
+    }
+
+    /// Resolved and voided markets are both settled.
+    pub fn is_settled(self) -> bool {
+        self != Self::Open
+    }
+
+    pub fn resolve(self) -> Result<Self, MarketError> {
+        match self {
+            Self::Open => Ok(Self::Resolved),
+            Self::Resolved => Err(MarketError::AlreadyResolved),
+            Self::Voided => Err(MarketError::Voided),
+        }
+    }
+
+    pub fn void(self) -> Result<Self, MarketError> {
+        match self {
+            Self::Open => Ok(Self::Voided),
+            Self::Resolved | Self::Voided => Err(MarketError::AlreadyResolved),
+        }
+    }
+
+    /// Bets are accepted until a market is voided.
+    pub fn check_not_voided(self) -> Result<(), MarketError> {
+        match self {
+            Self::Voided => Err(MarketError::Voided),
+            _ => Ok(()),
+        }
+    }
+
+    /// Cancellations refund the stake, so they end once a market settles.
+    pub fn check_open(self) -> Result<(), MarketError> {
+        match self {
+            Self::Open => Ok(()),
+            Self::Resolved => Err(MarketError::AlreadyResolved),
+            Self::Voided => Err(MarketError::Voided),
+        }
+    }
+
+    /// Payouts and fee claims need a settled market.
+    pub fn check_settled(self) -> Result<(), MarketError> {
+        match self.is_settled() {
+            true => Ok(()),
+            false => Err(MarketError::NotResolved),
+        }
+    }
+}
+
+/// Whether a market that started at `start_time` accepts bets at
+/// `current_time`. Both ends of the period are inclusive.
+pub fn is_active(start_time: u64, duration: u64, current_time: u64) -> bool {
+    (start_time..=start_time.saturating_add(duration)).contains(&current_time)
+}
+
+/// Whether the betting period of a market that started at `start_time` is
+/// over at `current_time`, so that it can be resolved.
+pub fn is_expired(start_time: u64, duration: u64, current_time: u64) -> bool {
+    current_time > start_time.saturating_add(duration)
+}
+
+/// Fails while the outcome can still change, i.e. during the dispute window or
+/// while a challenge awaits settlement.
+pub fn check_outcome_final(
+    disputed: bool,
+    dispute_deadline: i64,
+    current_time: i64,
+) -> Result<(), MarketError> {
+    if disputed {
+        return Err(MarketError::Disputed);
+    }
+
+    if current_time < dispute_deadline {
+        return Err(MarketError::DisputeWindowOpen);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [MarketStatus; 3] = [
+        MarketStatus::Open,
+        MarketStatus::Resolved,
+        MarketStatus::Voided,
+    ];
+
+    #[test]
+    fn status_byte_round_trips() {
+        for status in ALL {
+            assert_eq!(MarketStatus::from_u8(status as u8), status);
+        }
+    }
+
+    #[test]
+    fn only_open_markets_resolve() {
+        assert_eq!(MarketStatus::Open.resolve(), Ok(MarketStatus::Resolved));
+        assert_eq!(
+            MarketStatus::Resolved.resolve(),
+            Err(MarketError::AlreadyResolved)
+        );
+        assert_eq!(MarketStatus::Voided.resolve(), Err(MarketError::Voided));
+    }
+
+    #[test]
+    fn only_open_markets_void() {
+        assert_eq!(MarketStatus::Open.void(), Ok(MarketStatus::Voided));
+        assert_eq!(
+            MarketStatus::Resolved.void(),
+            Err(MarketError::AlreadyResolved)
+        );
+        assert_eq!(
+            MarketStatus::Voided.void(),
+            Err(MarketError::AlreadyResolved)
+        );
+    }
+
+    #[test]
+    fn settled_states_are_final() {
+        for status in ALL.into_iter().filter(|status| status.is_settled()) {
+            assert!(status.resolve().is_err());
+            assert!(status.void().is_err());
+            assert_eq!(status.check_settled(), Ok(()));
+        }
+
+        assert_eq!(
+            MarketStatus::Open.check_settled(),
+            Err(MarketError::NotResolved)
+        );
+    }
+
+    #[test]
+    fn voided_markets_reject_bets() {
+        assert_eq!(MarketStatus::Open.check_not_voided(), Ok(()));
+        assert_eq!(MarketStatus::Resolved.check_not_voided(), Ok(()));
+        assert_eq!(
+            MarketStatus::Voided.check_not_voided(),
+            Err(MarketError::Voided)
+        );
+    }
+
+    #[test]
+    fn settled_markets_reject_cancellations() {
+        assert_eq!(MarketStatus::Open.check_open(), Ok(()));
+        assert_eq!(
+            MarketStatus::Resolved.check_open(),
+            Err(MarketError::AlreadyResolved)
+        );
+        assert_eq!(MarketStatus::Voided.check_open(), Err(MarketError::Voided));
+    }
+
+    #[test]
+    fn active_period_is_inclusive() {
+        assert!(!is_active(100, 60, 99));
+        assert!(is_active(100, 60, 100));
+        assert!(is_active(100, 60, 160));
+        assert!(!is_active(100, 60, 161));
+        assert!(is_active(100, u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn markets_expire_after_the_active_period() {
+        assert!(!is_expired(100, 60, 160));
+        assert!(is_expired(100, 60, 161));
+        assert!(!is_expired(100, u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn outcome_is_final_after_the_dispute_window() {
+        assert_eq!(
+            check_outcome_final(false, 100, 99),
+            Err(MarketError::DisputeWindowOpen)
+        );
+        assert_eq!(check_outcome_final(false, 100, 100), Ok(()));
+        assert_eq!(
+            check_outcome_final(true, 100, 200),
+            Err(MarketError::Disputed)
+        );
+    }
+}
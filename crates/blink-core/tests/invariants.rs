@@ -0,0 +1,164 @@
+use blink_core::fees::{calculate_fee_rebate, calculate_fees, FeeRates};
+use blink_core::payout::calculate_payout;
+use blink_core::pricing::confidence_within;
+use blink_core::refund::calculate_refund_amount;
+use blink_core::state::MarketStatus;
+use blink_core::BPS_DENOMINATOR;
+use proptest::prelude::*;
+
+/// Every schedule the program accepts charges at most the whole pool.
+fn fee_rates() -> impl Strategy<Value = FeeRates> {
+    (0..=2_500u16, 0..=2_500u16, 0..=2_500u16, 0..=2_500u16).prop_map(
+        |(protocol_bps, creator_bps, referrer_bps, resolver_bps)| FeeRates {
+            protocol_bps,
+            creator_bps,
+            referrer_bps,
+            resolver_bps,
+        },
+    )
+}
+
+/// Bets of up to a hundred million SOL each, split between both sides.
+fn bets() -> impl Strategy<Value = Vec<(u64, bool)>> {
+    prop::collection::vec((1..=100_000_000_000_000_000u64, any::<bool>()), 1..32)
+}
+
+fn status() -> impl Strategy<Value = MarketStatus> {
+    prop_oneof![
+        Just(MarketStatus::Open),
+        Just(MarketStatus::Resolved),
+        Just(MarketStatus::Voided),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn fees_never_exceed_pool(pool: u64, referred_volume: u64, rates in fee_rates()) {
+        let fees = calculate_fees(pool, referred_volume, &rates);
+
+        prop_assert!(fees.total() as u128 <= pool as u128 * rates.total_bps() as u128 / BPS_DENOMINATOR as u128);
+    }
+
+    #[test]
+    fn referrals_only_move_fees_between_referrer_and_protocol(
+        pool: u64,
+        referred_volume: u64,
+        rates in fee_rates(),
+    ) {
+        let referred = calculate_fees(pool, referred_volume, &rates);
+        let unreferred = calculate_fees(pool, 0, &rates);
+
+        prop_assert_eq!(referred.creator, unreferred.creator);
+        prop_assert_eq!(referred.resolver, unreferred.resolver);
+        prop_assert!(referred.total() <= unreferred.total());
+        prop_assert!(unreferred.total() - referred.total() <= 1);
+    }
+
+    #[test]
+    fn payouts_never_exceed_pool(bets in bets(), rates in fee_rates(), yes_wins: bool) {
+        let pool: u64 = bets.iter().map(|&(amount, _)| amount).sum();
+        let winning_side: u64 = bets
+            .iter()
+            .filter(|&&(_, choice)| choice == yes_wins)
+            .map(|&(amount, _)| amount)
+            .sum();
+        let fees = calculate_fees(pool, 0, &rates).total();
+
+        let mut paid = 0u64;
+        let mut withheld = 0u64;
+        for &(amount, choice) in &bets {
+            let shares = if choice == yes_wins { amount } else { 0 };
+            let payout = calculate_payout(shares, winning_side, pool, fees);
+            paid += payout.amount;
+            withheld += payout.fee;
+        }
+
+        prop_assert!(paid <= pool - fees);
+        prop_assert!(paid + withheld <= pool);
+        if winning_side > 0 {
+            // rounding leaves at most a lamport per winner in the market, and
+            // counts it as withheld
+            prop_assert!(pool - fees - paid <= bets.len() as u64);
+            prop_assert!(withheld <= fees + bets.len() as u64);
+        }
+    }
+
+    #[test]
+    fn payout_grows_with_shares(
+        winning_side in 1..=u64::MAX / 2,
+        pool in 0..=u64::MAX / 2,
+        fee_bps in 0..=10_000u64,
+        a: u64,
+        b: u64,
+    ) {
+        let fees = (pool as u128 * fee_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let (low, high) = (a.min(b) % (winning_side + 1), a.max(b) % (winning_side + 1));
+        let (low, high) = (low.min(high), low.max(high));
+
+        let low_payout = calculate_payout(low, winning_side, pool, fees);
+        let high_payout = calculate_payout(high, winning_side, pool, fees);
+
+        prop_assert!(low_payout.amount <= high_payout.amount);
+        prop_assert!(high_payout.amount + high_payout.fee <= pool);
+    }
+
+    #[test]
+    fn rebate_never_exceeds_fee(fee: u64, discount_bps in 0..=10_000u16) {
+        prop_assert!(calculate_fee_rebate(fee, discount_bps) <= fee);
+    }
+
+    #[test]
+    fn refund_never_exceeds_stake(amount: u64, elapsed: u64, duration: u64) {
+        prop_assert!(calculate_refund_amount(amount, elapsed, duration) <= amount);
+    }
+
+    #[test]
+    fn refund_shrinks_over_time(
+        amount in 0..=1_000_000_000_000_000_000u64,
+        duration in 1..=30 * 24 * 60 * 60u64,
+        a: u64,
+        b: u64,
+    ) {
+        let (earlier, later) = (a.min(b) % (duration + 1), a.max(b) % (duration + 1));
+        let (earlier, later) = (earlier.min(later), earlier.max(later));
+
+        prop_assert!(
+            calculate_refund_amount(amount, earlier, duration)
+                >= calculate_refund_amount(amount, later, duration)
+        );
+    }
+
+    #[test]
+    fn wider_confidence_limits_accept_more(price: i64, conf: u64, a: u16, b: u16) {
+        let (tight, loose) = (a.min(b), a.max(b));
+
+        if confidence_within(price, conf, tight) {
+            prop_assert!(confidence_within(price, conf, loose));
+        }
+    }
+
+    #[test]
+    fn markets_settle_at_most_once(actions in prop::collection::vec(any::<bool>(), 0..8)) {
+        let mut status = MarketStatus::Open;
+        let mut settlements = 0;
+
+        for resolve in actions {
+            let next = if resolve { status.resolve() } else { status.void() };
+            if let Ok(next) = next {
+                prop_assert!(next.is_settled());
+                status = next;
+                settlements += 1;
+            }
+        }
+
+        prop_assert!(settlements <= 1);
+    }
+
+    #[test]
+    fn failed_transitions_do_not_unsettle(status in status()) {
+        for next in [status.resolve(), status.void()].into_iter().flatten() {
+            prop_assert_eq!(status, MarketStatus::Open);
+            prop_assert!(next.is_settled());
+        }
+    }
+}
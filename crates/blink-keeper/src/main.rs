@@ -0,0 +1,214 @@
+//! Keeps blink markets moving without manual calls: resolves markets once
+//! their betting period ends and withdraws the protocol fee of resolved
+//! markets once it unlocks.
+//!
+//! The signer must hold the resolver role to resolve markets and the treasury
+//! role to withdraw fees; jobs whose role is missing are skipped. Point
+//! `--url` at a local validator to run it against test markets.
+
+mod schedule;
+mod sender;
+
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context as _};
+use blink_client::accounts::{fetch_config, fetch_markets, MarketFilter};
+use blink_client::state::{Market, MarketStatus, Role};
+use blink_client::{instructions, pda};
+use clap::Parser;
+use log::{error, info, warn};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::from_account;
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::sysvar;
+
+use crate::schedule::{due_for_fee_withdrawal, due_for_resolution};
+use crate::sender::Sender;
+
+#[derive(Parser)]
+#[command(name = "blink-keeper", version, about)]
+struct Args {
+    #[arg(short, long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Defaults to the Solana CLI's keypair.
+    #[arg(short, long)]
+    keypair: Option<PathBuf>,
+    /// Seconds between scans.
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+    /// Run a single scan and exit.
+    #[arg(long)]
+    once: bool,
+    /// Priority fee in micro-lamports per compute unit.
+    #[arg(long, default_value_t = 0)]
+    priority_fee: u64,
+    #[arg(long, default_value_t = 200_000)]
+    compute_unit_limit: u32,
+    /// Retries of a transaction after transient failures. Program errors
+    /// are never retried.
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+    /// Markets per fee withdrawal transaction.
+    #[arg(long, default_value_t = 8)]
+    batch_size: usize,
+    /// Only resolve markets.
+    #[arg(long)]
+    skip_fees: bool,
+}
+
+struct Keeper<'a> {
+    rpc: &'a RpcClient,
+    sender: Sender<'a>,
+    resolve: bool,
+    withdraw: bool,
+    batch_size: usize,
+}
+
+impl Keeper<'_> {
+    /// The cluster's clock rather than the local one, since that is what
+    /// the program checks against.
+    fn current_time(&self) -> anyhow::Result<i64> {
+        let account = self.rpc.get_account(&sysvar::clock::ID)?;
+        let clock: Clock = from_account(&account).ok_or_else(|| anyhow!("invalid clock sysvar"))?;
+        Ok(clock.unix_timestamp)
+    }
+
+    fn markets(&self, status: MarketStatus) -> anyhow::Result<Vec<(Pubkey, Market)>> {
+        let filter = MarketFilter {
+            status: Some(status),
+            ..MarketFilter::default()
+        };
+        fetch_markets(self.rpc, &filter).context("fetching markets")
+    }
+
+    fn scan(&self) -> anyhow::Result<()> {
+        let current_time = self.current_time()?;
+
+        if self.resolve {
+            if fetch_config(self.rpc)?.pause.paused {
+                info!("program is paused, not resolving markets");
+            } else {
+                self.resolve_expired(current_time)?;
+            }
+        }
+        if self.withdraw {
+            self.withdraw_fees(current_time)?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_expired(&self, current_time: i64) -> anyhow::Result<()> {
+        let resolver = self.sender.signer.pubkey();
+
+        for (address, market) in self.markets(MarketStatus::Open)? {
+            if !due_for_resolution(&market, current_time) {
+                continue;
+            }
+            let ix = instructions::resolve_market(&resolver, &address, &market.feed_id);
+            match self.sender.send(&[ix]) {
+                Ok(signature) => info!("resolved {address} ({}): {signature}", market.symbol()),
+                Err(error) => error!("resolving {address} failed: {error:#}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn withdraw_fees(&self, current_time: i64) -> anyhow::Result<()> {
+        let authority = self.sender.signer.pubkey();
+        let due: Vec<Pubkey> = self
+            .markets(MarketStatus::Resolved)?
+            .into_iter()
+            .filter(|(_, market)| due_for_fee_withdrawal(market, current_time))
+            .map(|(address, _)| address)
+            .collect();
+
+        for batch in due.chunks(self.batch_size) {
+            let ix = instructions::withdraw_team_fees(&authority, batch);
+            match self.sender.send(&[ix]) {
+                Ok(signature) => info!("withdrew fees of {} markets: {signature}", batch.len()),
+                // one market failing its checks fails the whole batch, so
+                // the rest are withdrawn one by one
+                Err(error) if batch.len() > 1 => {
+                    warn!("batch withdrawal failed, withdrawing individually: {error:#}");
+                    for market in batch {
+                        let ix = instructions::withdraw_team_fee(&authority, market);
+                        match self.sender.send(&[ix]) {
+                            Ok(signature) => info!("withdrew fees of {market}: {signature}"),
+                            Err(error) => error!("withdrawing fees of {market} failed: {error:#}"),
+                        }
+                    }
+                }
+                Err(error) => error!("withdrawing fees of {} failed: {error:#}", batch[0]),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn has_role(rpc: &RpcClient, role: Role, member: &Pubkey) -> anyhow::Result<bool> {
+    let address = pda::role_address(role, member).0;
+    Ok(rpc
+        .get_account_with_commitment(&address, rpc.commitment())?
+        .value
+        .is_some())
+}
+
+fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let args = Args::parse();
+
+    let keypair = args.keypair.unwrap_or_else(|| {
+        PathBuf::from(std::env::var_os("HOME").unwrap_or_default()).join(".config/solana/id.json")
+    });
+    let signer: Keypair = read_keypair_file(&keypair)
+        .map_err(|error| anyhow!("{error}"))
+        .with_context(|| format!("reading keypair {}", keypair.display()))?;
+    let rpc = RpcClient::new_with_commitment(args.url, CommitmentConfig::confirmed());
+
+    let resolve = has_role(&rpc, Role::Resolver, &signer.pubkey())?;
+    let withdraw = !args.skip_fees && has_role(&rpc, Role::Treasury, &signer.pubkey())?;
+    if !resolve {
+        warn!(
+            "{} lacks the resolver role, not resolving markets",
+            signer.pubkey()
+        );
+    }
+    if !args.skip_fees && !withdraw {
+        warn!(
+            "{} lacks the treasury role, not withdrawing fees",
+            signer.pubkey()
+        );
+    }
+
+    let keeper = Keeper {
+        rpc: &rpc,
+        sender: Sender {
+            rpc: &rpc,
+            signer: &signer,
+            priority_fee: args.priority_fee,
+            compute_unit_limit: args.compute_unit_limit,
+            max_retries: args.max_retries,
+        },
+        resolve,
+        withdraw,
+        batch_size: args.batch_size.max(1),
+    };
+
+    loop {
+        if let Err(error) = keeper.scan() {
+            error!("scan failed: {error:#}");
+        }
+        if args.once {
+            return Ok(());
+        }
+        sleep(Duration::from_secs(args.interval));
+    }
+}
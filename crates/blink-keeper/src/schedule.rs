@@ -0,0 +1,89 @@
+//! Which markets need the keeper. These mirror the program's own checks so
+//! the keeper only submits transactions that can succeed.
+
+use blink_client::blink_core::state::is_expired;
+use blink_client::state::{Market, MarketStatus};
+
+/// Open markets whose betting period has ended.
+pub fn due_for_resolution(market: &Market, current_time: i64) -> bool {
+    market.status() == MarketStatus::Open
+        && is_expired(market.start_time, market.duration, current_time as u64)
+        && !market.pause().paused
+}
+
+/// Settled markets with an unpaid protocol fee whose timelock and dispute
+/// window have both passed. Voided markets only owe their cancellation
+/// penalties.
+pub fn due_for_fee_withdrawal(market: &Market, current_time: i64) -> bool {
+    market.is_resolved()
+        && market.team_fee_paid == 0
+        && current_time >= market.team_fee_unlock_time
+        && market.check_outcome_final(current_time).is_ok()
+        && market.protocol_fee() > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use blink_client::state::FeeSchedule;
+    use bytemuck::Zeroable;
+
+    use super::*;
+
+    fn market(status: MarketStatus) -> Market {
+        let mut market = Market::zeroed();
+        market.set_status(status);
+        market.start_time = 1_000;
+        market.duration = 60;
+        market.total_yes_shares = 1_000_000;
+        market.fee_schedule = FeeSchedule {
+            protocol_bps: 100,
+            ..FeeSchedule::default()
+        };
+        market
+    }
+
+    #[test]
+    fn markets_resolve_only_after_their_last_second() {
+        let open = market(MarketStatus::Open);
+
+        assert!(!due_for_resolution(&open, 1_030));
+        assert!(!due_for_resolution(&open, 1_060));
+        assert!(due_for_resolution(&open, 1_061));
+        assert!(!due_for_resolution(&market(MarketStatus::Resolved), 1_061));
+    }
+
+    #[test]
+    fn paused_markets_are_not_resolved() {
+        let mut open = market(MarketStatus::Open);
+        open.paused = 1;
+
+        assert!(!due_for_resolution(&open, 2_000));
+    }
+
+    #[test]
+    fn fees_are_withdrawn_once_unlocked_and_final() {
+        let mut resolved = market(MarketStatus::Resolved);
+        resolved.team_fee_unlock_time = 2_000;
+        resolved.dispute_deadline = 2_500;
+
+        assert!(!due_for_fee_withdrawal(&resolved, 1_999));
+        assert!(!due_for_fee_withdrawal(&resolved, 2_000));
+        assert!(due_for_fee_withdrawal(&resolved, 2_500));
+
+        resolved.disputed = 1;
+        assert!(!due_for_fee_withdrawal(&resolved, 3_000));
+        resolved.disputed = 0;
+
+        resolved.team_fee_paid = 1;
+        assert!(!due_for_fee_withdrawal(&resolved, 3_000));
+    }
+
+    #[test]
+    fn voided_markets_owe_only_cancellation_penalties() {
+        let mut voided = market(MarketStatus::Voided);
+        assert!(!due_for_fee_withdrawal(&voided, 3_000));
+
+        voided.cancellation_penalties = 1_000;
+        assert!(due_for_fee_withdrawal(&voided, 3_000));
+    }
+}
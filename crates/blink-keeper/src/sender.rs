@@ -0,0 +1,78 @@
+//! Sends transactions with a priority fee, retrying transient failures with
+//! a fresh blockhash. Program errors are final and returned straight away.
+
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use blink_client::ClientError;
+use log::warn;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+pub struct Sender<'a> {
+    pub rpc: &'a RpcClient,
+    pub signer: &'a Keypair,
+    /// Micro-lamports per compute unit; zero sends without a priority fee.
+    pub priority_fee: u64,
+    pub compute_unit_limit: u32,
+    pub max_retries: u32,
+}
+
+impl Sender<'_> {
+    fn budget(&self) -> Vec<Instruction> {
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            self.compute_unit_limit,
+        )];
+        if self.priority_fee > 0 {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                self.priority_fee,
+            ));
+        }
+        instructions
+    }
+
+    pub fn send(&self, instructions: &[Instruction]) -> anyhow::Result<Signature> {
+        let mut all = self.budget();
+        all.extend_from_slice(instructions);
+
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            let error = match self.try_send(&all) {
+                Ok(signature) => return Ok(signature),
+                Err(error) => error,
+            };
+            if let Some(code) = error.program_error() {
+                return Err(anyhow!("{}: {}", code.name(), code));
+            }
+            if attempt == self.max_retries {
+                return Err(error.into());
+            }
+
+            attempt += 1;
+            warn!(
+                "attempt {attempt} of {} failed, retrying in {backoff:?}: {error}",
+                self.max_retries
+            );
+            sleep(backoff);
+            backoff *= 2;
+        }
+    }
+
+    fn try_send(&self, instructions: &[Instruction]) -> Result<Signature, ClientError> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.signer.pubkey()),
+            &[self.signer],
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+}
@@ -5,10 +5,36 @@ pub static TEAM_WALLET: Pubkey =
 pub static MARKET_CREATION_AUTHORITY: Pubkey =
     solana_program::pubkey!("3xPuKYdk1yBQBtb9g1y1HZQt2udSa8aJLLVKm6mtKaVs");
 
-pub const MARKET_CREATION_FEE: u64 = 100_000_000;
 pub const MIN_BET_AMOUNT: u64 = 1_000_000;
 pub const STALENESS_THRESHOLD: u64 = 60 * 5;
 
+pub use blink_core::BPS_DENOMINATOR;
+pub const MAX_TOTAL_FEE_BPS: u64 = 2_000;
+pub const MAX_FEE_TIERS: usize = 8;
+
+pub const MIN_MARKET_DURATION: u64 = 60;
+pub const MAX_MARKET_DURATION: u64 = 30 * 24 * 60 * 60;
+
+pub const MAX_GOVERNANCE_DELAY: i64 = 30 * 24 * 60 * 60;
+pub const MAX_DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60;
+// the protocol fee can be withdrawn this long after a market resolves
+pub const TEAM_FEE_TIMELOCK: i64 = 7 * 24 * 60 * 60;
+
+// versioned accounts store their version right after the discriminator
+pub const ACCOUNT_VERSION_OFFSET: usize = 8;
+
+pub const MAX_SYMBOL_LEN: usize = 16;
+// base58-encoded pubkeys are at most 44 characters long
+pub const MAX_FEED_ID_LEN: usize = 44;
+pub const MAX_REFERRAL_CODE_LEN: usize = 16;
+
+pub const CONFIG_PDA_SEED: &str = "config";
 pub const MARKET_PDA_SEED: &str = "market";
 pub const PRICE_FEED_CONFIG_PDA_SEED: &str = "price_feed_config";
 pub const USER_POSITION_PDA_SEED: &str = "user_position";
+pub const REFERRAL_PDA_SEED: &str = "referral";
+pub const BET_RECEIPT_PDA_SEED: &str = "bet_receipt";
+pub const USER_PROFILE_PDA_SEED: &str = "user_profile";
+pub const ROLE_PDA_SEED: &str = "role";
+pub const PROPOSAL_PDA_SEED: &str = "proposal";
+pub const DISPUTE_PDA_SEED: &str = "dispute";
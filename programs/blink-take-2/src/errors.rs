@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use blink_core::state::MarketError;
 
 #[error_code]
 pub enum ErrorCode {
@@ -36,4 +37,95 @@ pub enum ErrorCode {
     PythError,
     #[msg("Program should not try to serialize a price account")]
     TryToSerializePriceAccount,
+    #[msg("Invalid fee schedule")]
+    InvalidFeeSchedule,
+    #[msg("Fee already claimed")]
+    FeeAlreadyClaimed,
+    #[msg("Signer is not the market resolver")]
+    NotMarketResolver,
+    #[msg("Users cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referrer account is required for a new referral")]
+    ReferrerMissing,
+    #[msg("Referral account is required for a referred position")]
+    ReferralAccountMissing,
+    #[msg("Referrer does not match the referral")]
+    InvalidReferrer,
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Market has been voided")]
+    MarketVoided,
+    #[msg("Creator bond already settled")]
+    CreatorBondAlreadySettled,
+    #[msg("Invalid symbol")]
+    InvalidSymbol,
+    #[msg("Invalid price feed parameters")]
+    InvalidPriceFeedParams,
+    #[msg("Price feed is disabled")]
+    PriceFeedDisabled,
+    #[msg("Price feed must be disabled before it is closed")]
+    PriceFeedStillEnabled,
+    #[msg("Price is stale")]
+    StalePrice,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Invalid referral code")]
+    InvalidReferralCode,
+    #[msg("Invalid fee tiers")]
+    InvalidFeeTiers,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Market is paused")]
+    MarketPaused,
+    #[msg("Admins cannot revoke their own admin role")]
+    CannotRevokeOwnAdminRole,
+    #[msg("Proposal is not queued")]
+    ProposalNotQueued,
+    #[msg("Proposal timelock has not expired")]
+    ProposalTimelockNotExpired,
+    #[msg("Proposal does not match the requested change")]
+    ProposalActionMismatch,
+    #[msg("Invalid governance delay")]
+    InvalidGovernanceDelay,
+    #[msg("Invalid exposure limits")]
+    InvalidExposureLimits,
+    #[msg("Bet exceeds the maximum bet per transaction")]
+    BetTooLarge,
+    #[msg("Bet exceeds the maximum position per user")]
+    PositionLimitExceeded,
+    #[msg("Bet exceeds the maximum market TVL")]
+    MarketTvlLimitExceeded,
+    #[msg("Bet exceeds the maximum imbalance between outcomes")]
+    MarketImbalanceLimitExceeded,
+    #[msg("Invalid dispute policy")]
+    InvalidDisputePolicy,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Market outcome is under dispute")]
+    MarketDisputed,
+    #[msg("Challenge must propose a different outcome")]
+    DisputeOutcomeUnchanged,
+    #[msg("Account has already been migrated")]
+    AccountAlreadyMigrated,
+    #[msg("Price feed does not match the market's feed")]
+    PriceFeedMismatch,
+    #[msg("Price feed still has open markets")]
+    PriceFeedInUse,
+    #[msg("Account version was never released")]
+    UnsupportedAccountVersion,
+}
+
+impl From<MarketError> for ErrorCode {
+    fn from(error: MarketError) -> Self {
+        match error {
+            MarketError::NotActive => ErrorCode::MarketNotActive,
+            MarketError::NotResolved => ErrorCode::MarketNotResolved,
+            MarketError::AlreadyResolved => ErrorCode::MarketAlreadyResolved,
+            MarketError::Voided => ErrorCode::MarketVoided,
+            MarketError::Disputed => ErrorCode::MarketDisputed,
+            MarketError::DisputeWindowOpen => ErrorCode::DisputeWindowOpen,
+        }
+    }
 }
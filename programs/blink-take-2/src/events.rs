@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    DisputePolicy, ExposureLimits, FeeSchedule, FeeTier, OracleSource, PauseReason, ProposalAction,
+    Role, VoidReason,
+};
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub market_id: u64,
+    pub price_feed: Pubkey,
+    pub symbol: String,
+    pub start_time: u64,
+    pub duration: u64,
+    pub initial_price: i64,
+    pub creator_bond: u64,
+    pub fee_schedule: FeeSchedule,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub sequence: u64,
+    pub choice: bool,
+    pub amount: u64,
+    pub shares: u64,
+    pub referrer: Option<Pubkey>,
+    pub total_yes_shares: u64,
+    pub total_no_shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BetCancelled {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    pub refund_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub resolver: Pubkey,
+    pub initial_price: i64,
+    pub final_price: i64,
+    pub winning_outcome: bool,
+    pub total_yes_shares: u64,
+    pub total_no_shares: u64,
+    pub dispute_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketVoided {
+    pub market: Pubkey,
+    pub reason: VoidReason,
+    pub slashed_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub fee_rebate: u64,
+    pub refund: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TeamFeeWithdrawn {
+    pub market: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeeClaimed {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResolverFeeClaimed {
+    pub market: Pubkey,
+    pub resolver: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    pub markets: u32,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorBondRefunded {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralCodeSet {
+    pub user: Pubkey,
+    pub referral_code: String,
+}
+
+#[event]
+pub struct PriceFeedRegistered {
+    pub price_feed_config: Pubkey,
+    pub price_feed: Pubkey,
+    pub symbol: String,
+    pub decimals: u8,
+    pub oracle_source: OracleSource,
+    pub max_staleness: u64,
+    pub max_confidence_bps: u16,
+}
+
+#[event]
+pub struct PriceFeedUpdated {
+    pub price_feed_config: Pubkey,
+    pub symbol: String,
+    pub decimals: u8,
+    pub oracle_source: OracleSource,
+    pub max_staleness: u64,
+    pub max_confidence_bps: u16,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct PriceFeedClosed {
+    pub price_feed_config: Pubkey,
+    pub price_feed: Pubkey,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub fee_schedule: FeeSchedule,
+    pub creation_bond: u64,
+    pub governance_delay: i64,
+    pub exposure_limits: ExposureLimits,
+    pub dispute_policy: DisputePolicy,
+}
+
+#[event]
+pub struct MarketLimitsUpdated {
+    pub market: Pubkey,
+    pub exposure_limits: ExposureLimits,
+}
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub fee_tiers: Vec<FeeTier>,
+}
+
+#[event]
+pub struct ProgramPaused {
+    pub guardian: Pubkey,
+    pub reason: PauseReason,
+    pub allow_claims: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramResumed {
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketPaused {
+    pub market: Pubkey,
+    pub guardian: Pubkey,
+    pub reason: PauseReason,
+    pub allow_claims: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketResumed {
+    pub market: Pubkey,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub member: Pubkey,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub member: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalQueued {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub market: Pubkey,
+    pub dispute: Pubkey,
+    pub challenger: Pubkey,
+    pub claimed_price: i64,
+    pub proposed_outcome: bool,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeSettled {
+    pub market: Pubkey,
+    pub dispute: Pubkey,
+    pub arbiter: Pubkey,
+    pub upheld: bool,
+    pub winning_outcome: bool,
+    pub refunded_bond: u64,
+    pub slashed_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionMigrated {
+    pub user_position: Pubkey,
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub deposited_yes: u64,
+    pub deposited_no: u64,
+    pub version: u8,
+}
+
+#[event]
+pub struct MarketMigrated {
+    pub market: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct PriceFeedMigrated {
+    pub price_feed_config: Pubkey,
+    pub price_feed: Pubkey,
+    pub version: u8,
+}
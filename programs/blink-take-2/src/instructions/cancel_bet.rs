@@ -1,64 +1,131 @@
 use anchor_lang::prelude::*;
+use blink_core::refund::{calculate_refund_amount, can_cancel};
 
-use crate::constants::{USER_POSITION_PDA_SEED, MARKET_PDA_SEED, MARKET_CREATION_AUTHORITY};
-use crate::state::{Market, UserPosition};
-use crate::utils::calculate_refund_amount;
+use crate::constants::{
+    CONFIG_PDA_SEED, MARKET_PDA_SEED, REFERRAL_PDA_SEED, USER_POSITION_PDA_SEED,
+    USER_PROFILE_PDA_SEED,
+};
 use crate::errors::ErrorCode;
+use crate::events::BetCancelled;
+use crate::state::{Config, Market, Referral, UserPosition, UserProfile};
 
 #[derive(Accounts)]
 pub struct CancelBet<'info> {
     #[account(
-      mut,
-      seeds = [
-        MARKET_PDA_SEED.as_bytes(), 
-        MARKET_CREATION_AUTHORITY.as_ref(), 
-        market.memecoin_symbol.as_bytes()
-      ],
-      bump
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
     )]
-    pub market: Account<'info, Market>,
+    pub market: AccountLoader<'info, Market>,
     #[account(
         mut,
         seeds = [USER_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), user.key().as_ref()],
-        bump,
+        bump = user_position.bump,
         has_one = market,
         has_one = user,
     )]
     pub user_position: Account<'info, UserPosition>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [USER_PROFILE_PDA_SEED.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [CONFIG_PDA_SEED.as_bytes()], bump)]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [REFERRAL_PDA_SEED.as_bytes(), user_position.key().as_ref()],
+        bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
 }
 
 pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
-    let market = &mut ctx.accounts.market;
+    let market = &mut ctx.accounts.market.load_mut()?;
     let user_position = &mut ctx.accounts.user_position;
 
-    let current_time = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.config.check_not_paused(market, false)?;
+
+    market.check_open()?;
+
+    // a claimed position has already been paid out of the pool
+    if user_position.claimed {
+        return Err(ErrorCode::AlreadyClaimed.into());
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let current_time = current_timestamp as u64;
     let elapsed_time = current_time - market.start_time;
-    let six_hours_in_seconds = 6 * 60 * 60;
 
-    if elapsed_time > six_hours_in_seconds {
+    if !can_cancel(elapsed_time) {
         return Err(ErrorCode::MarketAlreadyStarted.into());
     }
 
-    let total_shares = user_position.yes_shares + user_position.no_shares;
+    let cost_basis = user_position.cost_basis();
 
     // FIXME: is this correct? why is the 1.5 exponent thingy added?
-    let refund_amount = calculate_refund_amount(total_shares, elapsed_time, market.duration);
+    let refund_amount = calculate_refund_amount(cost_basis, elapsed_time, market.duration);
+
+    // cancelled volume no longer pays fees, so it stops earning referral rewards
+    match ctx.accounts.referral.as_mut() {
+        Some(referral) => {
+            market.referred_volume -= referral.volume;
+            referral.volume = 0;
+        }
+        None if user_position.referred => return Err(ErrorCode::ReferralAccountMissing.into()),
+        None => {}
+    }
 
     market.total_yes_shares -= user_position.yes_shares;
     market.total_no_shares -= user_position.no_shares;
+    market.cancellation_penalties += cost_basis - refund_amount;
 
-    **market.to_account_info().try_borrow_mut_lamports()? -= refund_amount;
+    **ctx
+        .accounts
+        .market
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= refund_amount;
     **ctx
         .accounts
         .user
         .to_account_info()
         .try_borrow_mut_lamports()? += refund_amount;
 
+    emit!(BetCancelled {
+        market: ctx.accounts.market.key(),
+        user: user_position.user,
+        yes_shares: user_position.yes_shares,
+        no_shares: user_position.no_shares,
+        refund_amount,
+        timestamp: current_timestamp,
+    });
+
+    // the cancellation penalty is a realized loss, and the cancelled stake no
+    // longer counts as volume. Positions migrated from before profiles were
+    // never counted, hence the saturation.
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.user = ctx.accounts.user.key();
+    user_profile.realized_pnl -= (cost_basis - refund_amount) as i64;
+    if cost_basis > 0 {
+        user_profile.total_volume = user_profile.total_volume.saturating_sub(cost_basis);
+        user_profile.markets_entered = user_profile.markets_entered.saturating_sub(1);
+    }
+
     user_position.yes_shares = 0;
     user_position.no_shares = 0;
+    user_position.deposited_yes = 0;
+    user_position.deposited_no = 0;
+    user_position.refunded += refund_amount;
 
     Ok(())
 }
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::ProposalCancelled;
+use crate::state::{Proposal, ProposalStatus, Role, RoleAssignment};
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    pub authority: Signer<'info>,
+}
+
+pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+
+    if proposal.status != ProposalStatus::Queued {
+        return Err(ErrorCode::ProposalNotQueued.into());
+    }
+
+    proposal.status = ProposalStatus::Cancelled;
+
+    emit!(ProposalCancelled {
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        cancelled_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use blink_core::pricing::winning_outcome;
+
+use crate::constants::{
+    CONFIG_PDA_SEED, DISPUTE_PDA_SEED, MARKET_CREATION_AUTHORITY, MARKET_PDA_SEED,
+    PRICE_FEED_CONFIG_PDA_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::DisputeOpened;
+use crate::state::{Config, Dispute, Market, PriceFeed, PriceFeedConfig};
+use crate::utils::fetch_pyth_price;
+
+#[derive(Accounts)]
+pub struct ChallengeOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [DISPUTE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(seeds = [CONFIG_PDA_SEED.as_bytes()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            price_feed.key().as_ref()
+        ],
+        bump = price_feed_config.bump
+    )]
+    pub price_feed_config: Account<'info, PriceFeedConfig>,
+    #[account(
+        address = price_feed_config.price_feed,
+        constraint = price_feed.key() == market.load()?.feed_id @ ErrorCode::PriceFeedMismatch
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Disputes a resolution with the price the market's own feed reports now,
+/// read under the same staleness and confidence checks as resolution.
+pub fn challenge_outcome(ctx: Context<ChallengeOutcome>) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.config.check_not_paused(market, false)?;
+
+    market.check_settled()?;
+
+    market.check_not_voided()?;
+
+    if current_time >= market.dispute_deadline {
+        return Err(ErrorCode::DisputeWindowClosed.into());
+    }
+
+    let claimed_price = fetch_pyth_price(
+        &ctx.accounts.price_feed.to_account_info(),
+        &ctx.accounts.price_feed_config,
+    )?;
+    let proposed_outcome = winning_outcome(market.initial_price, claimed_price);
+    if market.winning_outcome() == Some(proposed_outcome) {
+        return Err(ErrorCode::DisputeOutcomeUnchanged.into());
+    }
+
+    market.disputed = 1;
+
+    let bond = ctx.accounts.config.dispute_policy.bond;
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.market = ctx.accounts.market.key();
+    dispute.challenger = ctx.accounts.challenger.key();
+    dispute.claimed_price = claimed_price;
+    dispute.proposed_outcome = proposed_outcome;
+    dispute.bond = bond;
+    dispute.opened_at = current_time;
+
+    emit!(DisputeOpened {
+        market: ctx.accounts.market.key(),
+        dispute: dispute.key(),
+        challenger: dispute.challenger,
+        claimed_price,
+        proposed_outcome,
+        bond,
+        timestamp: current_time,
+    });
+
+    // the bond sits in the dispute account until the arbiter settles it
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.challenger.to_account_info(),
+            to: ctx.accounts.dispute.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context, bond)?;
+
+    Ok(())
+}
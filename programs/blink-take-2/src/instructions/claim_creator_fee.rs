@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MARKET_PDA_SEED;
+use crate::errors::ErrorCode;
+use crate::events::CreatorFeeClaimed;
+use crate::state::Market;
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFee<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump,
+        has_one = authority
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_creator_fee(ctx: Context<ClaimCreatorFee>) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.check_settled()?;
+
+    if market.creator_fee_paid != 0 {
+        return Err(ErrorCode::FeeAlreadyClaimed.into());
+    }
+
+    let creator_fee = market.fees().creator;
+
+    **ctx
+        .accounts
+        .market
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= creator_fee;
+    **ctx
+        .accounts
+        .authority
+        .to_account_info()
+        .try_borrow_mut_lamports()? += creator_fee;
+
+    market.creator_fee_paid = 1;
+
+    emit!(CreatorFeeClaimed {
+        market: ctx.accounts.market.key(),
+        creator: market.authority,
+        amount: creator_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
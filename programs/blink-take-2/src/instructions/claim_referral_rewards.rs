@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use blink_core::fees::calculate_referral_reward;
+
+use crate::errors::ErrorCode;
+use crate::events::ReferralRewardsClaimed;
+use crate::state::{Market, Referral};
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays out the referral rewards of every `(market, referral)` pair passed in
+/// `remaining_accounts` in a single transfer to the referrer.
+pub fn claim_referral_rewards<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimReferralRewards<'info>>,
+) -> Result<()> {
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+
+    if ctx.remaining_accounts.is_empty() || !pairs.remainder().is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    let referrer = &ctx.accounts.referrer;
+    let mut total_rewards: u64 = 0;
+
+    for accounts in pairs {
+        let market_loader = AccountLoader::<Market>::try_from(&accounts[0])?;
+        let market = market_loader.load()?;
+        let mut referral = Account::<Referral>::try_from(&accounts[1])?;
+
+        if referral.market != market_loader.key() {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+
+        if referral.referrer != referrer.key() {
+            return Err(ErrorCode::InvalidReferrer.into());
+        }
+
+        market.check_settled()?;
+
+        if referral.claimed {
+            return Err(ErrorCode::FeeAlreadyClaimed.into());
+        }
+
+        // voided markets pay no fees, so there is nothing to share with the referrer
+        let reward = match market.is_voided() {
+            true => 0,
+            false => calculate_referral_reward(referral.volume, market.fee_schedule.referrer_bps),
+        };
+
+        **market_loader.to_account_info().try_borrow_mut_lamports()? -= reward;
+        total_rewards += reward;
+
+        referral.claimed = true;
+        referral.exit(&crate::ID)?;
+    }
+
+    **referrer.to_account_info().try_borrow_mut_lamports()? += total_rewards;
+
+    emit!(ReferralRewardsClaimed {
+        referrer: referrer.key(),
+        markets: (ctx.remaining_accounts.len() / 2) as u32,
+        amount: total_rewards,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
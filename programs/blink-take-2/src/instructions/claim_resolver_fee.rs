@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MARKET_PDA_SEED;
+use crate::errors::ErrorCode;
+use crate::events::ResolverFeeClaimed;
+use crate::state::Market;
+
+#[derive(Accounts)]
+pub struct ClaimResolverFee<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump,
+        constraint = market.load()?.resolver() == Some(resolver.key()) @ ErrorCode::NotMarketResolver
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_resolver_fee(ctx: Context<ClaimResolverFee>) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.check_settled()?;
+
+    // an upheld dispute hands the resolver fee to the challenger instead
+    market.check_outcome_final(Clock::get()?.unix_timestamp)?;
+
+    if market.resolver_fee_paid != 0 {
+        return Err(ErrorCode::FeeAlreadyClaimed.into());
+    }
+
+    let resolver_fee = market.fees().resolver;
+
+    **ctx
+        .accounts
+        .market
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= resolver_fee;
+    **ctx
+        .accounts
+        .resolver
+        .to_account_info()
+        .try_borrow_mut_lamports()? += resolver_fee;
+
+    market.resolver_fee_paid = 1;
+
+    emit!(ResolverFeeClaimed {
+        market: ctx.accounts.market.key(),
+        resolver: ctx.accounts.resolver.key(),
+        amount: resolver_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
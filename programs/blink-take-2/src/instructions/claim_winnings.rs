@@ -1,87 +1,171 @@
 use anchor_lang::prelude::*;
+use blink_core::fees::{calculate_fee_rebate, resolve_fee_discount_bps};
+use blink_core::payout::{calculate_payout, Payout};
 
-use crate::constants::{USER_POSITION_PDA_SEED, MARKET_PDA_SEED, MARKET_CREATION_AUTHORITY};
+use crate::constants::{
+    CONFIG_PDA_SEED, MARKET_PDA_SEED, USER_POSITION_PDA_SEED, USER_PROFILE_PDA_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::state::{Market, UserPosition};
+use crate::events::WinningsClaimed;
+use crate::state::{Config, Market, UserPosition, UserProfile};
 
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
     #[account(
-      mut,
-      seeds = [
-        MARKET_PDA_SEED.as_bytes(), 
-        MARKET_CREATION_AUTHORITY.as_ref(), 
-        market.memecoin_symbol.as_bytes()
-      ],
-      bump
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
     )]
-    pub market: Account<'info, Market>,
+    pub market: AccountLoader<'info, Market>,
     #[account(
         mut,
         seeds = [
-          USER_POSITION_PDA_SEED.as_bytes(), 
-          market.key().as_ref(), 
-          user.key().as_ref()
+            USER_POSITION_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
         ],
-        bump,
+        bump = user_position.bump,
         has_one = market,
         has_one = user,
     )]
     pub user_position: Account<'info, UserPosition>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [USER_PROFILE_PDA_SEED.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [CONFIG_PDA_SEED.as_bytes()], bump)]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
-    let market = &mut ctx.accounts.market;
+    let market = &mut ctx.accounts.market.load_mut()?;
     let user_position = &mut ctx.accounts.user_position;
 
-    if !market.resolved {
-        return Err(ErrorCode::MarketNotResolved.into());
-    }
+    ctx.accounts.config.check_not_paused(market, true)?;
+
+    market.check_settled()?;
+
+    market.check_outcome_final(Clock::get()?.unix_timestamp)?;
 
     if user_position.claimed {
         return Err(ErrorCode::AlreadyClaimed.into());
     }
 
-    let winning_outcome = market.winning_outcome.ok_or(ErrorCode::MarketNotResolved)?;
-
-    let winning_shares: u64 = if winning_outcome {
-        user_position.yes_shares
-    } else {
-        user_position.no_shares
-    };
-
-    let total_winning_shares: u64 = if winning_outcome {
-        market.total_yes_shares
+    let Payout {
+        amount: winnings,
+        fee: fee_paid,
+    } = if market.is_voided() {
+        // voided markets refund every bettor's stake on both sides in full
+        Payout {
+            amount: user_position.cost_basis(),
+            fee: 0,
+        }
     } else {
-        market.total_no_shares
+        calculate_user_winnings(market, user_position)?
     };
 
-    let total_funds = market.to_account_info().lamports();
-    let winnings_pool = (total_funds * 95) / 100;
-    let team_fee = total_funds - winnings_pool;
+    // this stake is settled now, unless the market was voided and refunds it
+    let stake = user_position.cost_basis();
+    let user_profile = &mut ctx.accounts.user_profile;
+    if !market.is_voided() {
+        user_profile.settled_volume += stake;
+    }
 
-    let user_share = match total_winning_shares {
-      0 => 0,
-      _ => winning_shares * winnings_pool / total_winning_shares
+    // volume tiers are resolved against the live config, not the market snapshot,
+    // and are funded from the protocol share until it has been withdrawn.
+    // Volume still open elsewhere could be cancelled after this claim, so only
+    // settled volume counts.
+    let discount_bps = resolve_fee_discount_bps(
+        user_profile.settled_volume,
+        ctx.accounts
+            .config
+            .fee_tiers
+            .iter()
+            .map(|tier| (tier.min_volume, tier.discount_bps)),
+    );
+    let fee_rebate = match market.team_fee_paid != 0 {
+        true => 0,
+        false => calculate_fee_rebate(fee_paid, discount_bps).min(market.protocol_fee()),
     };
+    market.fee_rebates += fee_rebate;
 
-    if total_funds < user_share + team_fee {
+    let user_share = winnings + fee_rebate;
+
+    // the market's data is borrowed by `load_mut`, so its size comes from the layout
+    let market_info = ctx.accounts.market.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(8 + std::mem::size_of::<Market>());
+    if market_info.lamports() < user_share + rent_exempt_minimum + market.creator_bond {
         return Err(ErrorCode::InsufficientMarketFunds.into());
     }
 
-    **market.to_account_info().try_borrow_mut_lamports()? -= user_share;
+    **ctx
+        .accounts
+        .market
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= user_share;
     **ctx
         .accounts
         .user
         .to_account_info()
         .try_borrow_mut_lamports()? += user_share;
 
+    user_profile.user = ctx.accounts.user.key();
+    user_profile.realized_pnl += user_share as i64 - stake as i64;
+    // positions that never held a stake neither won nor lost
+    if !market.is_voided() && stake > 0 {
+        if user_share > stake {
+            user_profile.wins += 1;
+        } else {
+            user_profile.losses += 1;
+        }
+    }
+
     user_position.claimed = true;
+    user_position.claimed_amount = user_share;
     user_position.yes_shares = 0;
     user_position.no_shares = 0;
+    user_position.deposited_yes = 0;
+    user_position.deposited_no = 0;
+
+    emit!(WinningsClaimed {
+        market: ctx.accounts.market.key(),
+        user: user_position.user,
+        amount: user_share,
+        fee_rebate,
+        refund: market.is_voided(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
+
+/// Returns the user's winnings net of fees, and the fees withheld from them.
+fn calculate_user_winnings(market: &Market, user_position: &UserPosition) -> Result<Payout> {
+    let winning_outcome = market
+        .winning_outcome()
+        .ok_or(ErrorCode::MarketNotResolved)?;
+
+    let (winning_shares, total_winning_shares) = if winning_outcome {
+        (user_position.yes_shares, market.total_yes_shares)
+    } else {
+        (user_position.no_shares, market.total_no_shares)
+    };
+
+    Ok(calculate_payout(
+        winning_shares,
+        total_winning_shares,
+        market.total_pool(),
+        market.fees().total(),
+    ))
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{BET_RECEIPT_PDA_SEED, USER_POSITION_PDA_SEED};
+use crate::state::{BetReceipt, Market, UserPosition};
+
+#[derive(Accounts)]
+pub struct CloseBetReceipt<'info> {
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        seeds = [
+            USER_POSITION_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump = user_position.bump,
+        has_one = market,
+        has_one = user
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            BET_RECEIPT_PDA_SEED.as_bytes(),
+            user_position.key().as_ref(),
+            bet_receipt.sequence.to_le_bytes().as_ref()
+        ],
+        bump,
+        has_one = market,
+        has_one = user
+    )]
+    pub bet_receipt: Account<'info, BetReceipt>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn close_bet_receipt(ctx: Context<CloseBetReceipt>) -> Result<()> {
+    // receipts stay around until the market settles so bettors can audit their bets
+    ctx.accounts.market.load()?.check_settled()?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MARKET_CREATION_AUTHORITY, PRICE_FEED_CONFIG_PDA_SEED, ROLE_PDA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::PriceFeedClosed;
+use crate::state::{PriceFeedConfig, Role, RoleAssignment};
+
+#[derive(Accounts)]
+pub struct ClosePriceFeed<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            price_feed_config.price_feed.as_ref()
+        ],
+        bump = price_feed_config.bump
+    )]
+    pub price_feed_config: Account<'info, PriceFeedConfig>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Removes a disabled feed from the registry. Markets still open on it
+/// resolve against it, so it stays until they are all settled.
+pub fn close_price_feed(ctx: Context<ClosePriceFeed>) -> Result<()> {
+    if ctx.accounts.price_feed_config.enabled {
+        return Err(ErrorCode::PriceFeedStillEnabled.into());
+    }
+
+    if ctx.accounts.price_feed_config.open_markets > 0 {
+        return Err(ErrorCode::PriceFeedInUse.into());
+    }
+
+    emit!(PriceFeedClosed {
+        price_feed_config: ctx.accounts.price_feed_config.key(),
+        price_feed: ctx.accounts.price_feed_config.price_feed,
+    });
+
+    Ok(())
+}
@@ -1,77 +1,121 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{Market, PriceFeed, PriceFeedConfig};
-use crate::constants::{TEAM_WALLET, MARKET_PDA_SEED, MARKET_CREATION_FEE, MARKET_CREATION_AUTHORITY, PRICE_FEED_CONFIG_PDA_SEED};
-use crate::utils::fetch_pyth_price;
+use crate::constants::{
+    CONFIG_PDA_SEED, MARKET_CREATION_AUTHORITY, MARKET_PDA_SEED, PRICE_FEED_CONFIG_PDA_SEED,
+    ROLE_PDA_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::MarketCreated;
+use crate::state::{
+    Config, Market, MarketStatus, PriceFeed, PriceFeedConfig, Role, RoleAssignment,
+};
+use crate::utils::{fetch_pyth_price, symbol_to_bytes, validate_duration};
 
 #[derive(Accounts)]
-#[instruction(memecoin_symbol: String)]
+#[instruction(market_id: u64)]
 pub struct CreateMarket<'info> {
     #[account(
-        init, 
-        payer = authority, 
+        init,
+        payer = authority,
         space = 8 + std::mem::size_of::<Market>(),
         seeds = [
-          MARKET_PDA_SEED.as_bytes(),
-          authority.key().as_ref(), 
-          memecoin_symbol.as_bytes()
+            MARKET_PDA_SEED.as_bytes(),
+            authority.key().as_ref(),
+            market_id.to_le_bytes().as_ref()
         ],
         bump
     )]
-    pub market: Account<'info, Market>,
+    pub market: AccountLoader<'info, Market>,
+    #[account(seeds = [CONFIG_PDA_SEED.as_bytes()], bump)]
+    pub config: Account<'info, Config>,
     #[account(
-      seeds = [
-        PRICE_FEED_CONFIG_PDA_SEED.as_bytes(), 
-        MARKET_CREATION_AUTHORITY.key().as_ref(), 
-        price_feed.key().as_ref()
-      ],
-      bump
+        mut,
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            price_feed.key().as_ref()
+        ],
+        bump = price_feed_config.bump,
+        constraint = price_feed_config.enabled @ ErrorCode::PriceFeedDisabled
     )]
     pub price_feed_config: Account<'info, PriceFeedConfig>,
     #[account(address = price_feed_config.price_feed)]
     pub price_feed: Account<'info, PriceFeed>,
-    #[account(mut, address = MARKET_CREATION_AUTHORITY)]
+    /// Approved market creators skip the creation bond.
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::MarketCreator as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub creator_role: Option<Account<'info, RoleAssignment>>,
+    #[account(mut)]
     pub authority: Signer<'info>,
-    #[account(mut, address = TEAM_WALLET)]
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    pub team_wallet: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn create_market(
-  ctx: Context<CreateMarket>,
-  memecoin_symbol: String,
-  feed_id: String,
-  duration: u64,
-) -> Result<()> {
-  let market = &mut ctx.accounts.market;
-  let price_feed = &ctx.accounts.price_feed;
+pub fn create_market(ctx: Context<CreateMarket>, market_id: u64, duration: u64) -> Result<()> {
+    validate_duration(duration)?;
+
+    let creator_bond = if ctx.accounts.creator_role.is_some() {
+        0
+    } else {
+        ctx.accounts.config.creation_bond
+    };
 
-  let current_timestamp = Clock::get()?.unix_timestamp;
-  let price = fetch_pyth_price(&price_feed.to_account_info())?;
+    let price_feed = &ctx.accounts.price_feed;
+    let price_feed_config = &ctx.accounts.price_feed_config;
 
-  market.memecoin_symbol = memecoin_symbol;
-  market.feed_id = feed_id;
-  market.start_time = current_timestamp as u64;
-  market.duration = duration;
-  market.total_yes_shares = 0;
-  market.total_no_shares = 0;
-  market.resolved = false;
-  market.winning_outcome = None;
-  market.authority = ctx.accounts.authority.key();
-  market.initial_price = Some(price);
-  
-  // set team fee unlock time (7 days after market resolution)
-  market.team_fee_unlock_time = (market.start_time + market.duration + 7 * 24 * 60 * 60) as i64;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let price = fetch_pyth_price(&price_feed.to_account_info(), price_feed_config)?;
 
-  let cpi_context = CpiContext::new(
-      ctx.accounts.system_program.to_account_info(),
-      anchor_lang::system_program::Transfer {
-          from: ctx.accounts.authority.to_account_info(),
-          to: ctx.accounts.team_wallet.to_account_info(),
-      },
-  );
-  anchor_lang::system_program::transfer(cpi_context, MARKET_CREATION_FEE)?;
+    // zeroed on init, so only the non-default fields are written. The
+    // discriminator is only written on exit, so the market cannot be loaded
+    // again below and the event is built here.
+    let event = {
+        let market = &mut ctx.accounts.market.load_init()?;
+        market.version = Market::VERSION;
+        market.market_id = market_id;
+        market.memecoin_symbol = symbol_to_bytes(&price_feed_config.symbol);
+        market.feed_id = price_feed_config.price_feed;
+        market.start_time = current_timestamp as u64;
+        market.duration = duration;
+        market.set_status(MarketStatus::Open);
+        market.authority = ctx.accounts.authority.key();
+        market.initial_price = price;
+        market.fee_schedule = ctx.accounts.config.fee_schedule;
+        market.exposure_limits = ctx.accounts.config.exposure_limits;
+        market.creator_bond = creator_bond;
+        market.bump = ctx.bumps.market;
 
-  Ok(())
-}
\ No newline at end of file
+        // the team fee unlocks relative to resolution, see resolve_market
+        market.team_fee_unlock_time = 0;
+
+        MarketCreated {
+            market: ctx.accounts.market.key(),
+            creator: market.authority,
+            market_id,
+            price_feed: price_feed_config.price_feed,
+            symbol: market.symbol(),
+            start_time: market.start_time,
+            duration,
+            initial_price: price,
+            creator_bond: market.creator_bond,
+            fee_schedule: market.fee_schedule,
+        }
+    };
+
+    // anyone can create a market on a registered feed, backed by a refundable bond
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.market.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context, creator_bond)?;
+
+    ctx.accounts.price_feed_config.open_markets += 1;
+
+    emit!(event);
+
+    Ok(())
+}
@@ -5,15 +5,15 @@ use crate::state::{Market, UserPosition};
 
 #[derive(Accounts)]
 pub struct CreateUser<'info> {
-    pub market: Account<'info, Market>,
+    pub market: AccountLoader<'info, Market>,
     #[account(
         init,
         payer = user,
-        space = 8 + std::mem::size_of::<UserPosition>(),
+        space = 8 + UserPosition::INIT_SPACE,
         seeds = [
-          USER_POSITION_PDA_SEED.as_bytes(), 
-          market.key().as_ref(), 
-          user.key().as_ref()
+            USER_POSITION_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
         ],
         bump
     )]
@@ -25,14 +25,21 @@ pub struct CreateUser<'info> {
 
 pub fn create_user(ctx: Context<CreateUser>) -> Result<()> {
     let user_position = &mut ctx.accounts.user_position;
-    let market = &ctx.accounts.market;
     let user = &ctx.accounts.user;
 
-    user_position.market = market.key();
+    user_position.version = UserPosition::VERSION;
+    user_position.market = ctx.accounts.market.key();
     user_position.user = user.key();
     user_position.yes_shares = 0;
     user_position.no_shares = 0;
     user_position.claimed = false;
+    user_position.referred = false;
+    user_position.bet_count = 0;
+    user_position.deposited_yes = 0;
+    user_position.deposited_no = 0;
+    user_position.refunded = 0;
+    user_position.claimed_amount = 0;
+    user_position.bump = ctx.bumps.user_position;
 
     Ok(())
 }
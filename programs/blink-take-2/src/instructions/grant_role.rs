@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::RoleGranted;
+use crate::state::{execute_proposal, Proposal, ProposalAction, Role, RoleAssignment};
+
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[role as u8], member.as_ref()],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::GrantRole { role, member },
+    )?;
+
+    let role_assignment = &mut ctx.accounts.role_assignment;
+    let granted_at = Clock::get()?.unix_timestamp;
+
+    role_assignment.role = role;
+    role_assignment.member = member;
+    role_assignment.granted_by = ctx.accounts.authority.key();
+    role_assignment.granted_at = granted_at;
+
+    emit!(RoleGranted {
+        role,
+        member,
+        granted_by: ctx.accounts.authority.key(),
+        timestamp: granted_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_PDA_SEED, MARKET_CREATION_AUTHORITY, ROLE_PDA_SEED};
+use crate::events::{ConfigUpdated, RoleGranted};
+use crate::state::{
+    validate_governance_delay, Config, DisputePolicy, ExposureLimits, FeeSchedule, PauseState,
+    Role, RoleAssignment,
+};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [CONFIG_PDA_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    /// The bootstrap authority becomes the first admin and grants every other role.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(mut, address = MARKET_CREATION_AUTHORITY)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config(
+    ctx: Context<InitializeConfig>,
+    fee_schedule: FeeSchedule,
+    creation_bond: u64,
+    governance_delay: i64,
+    exposure_limits: ExposureLimits,
+    dispute_policy: DisputePolicy,
+) -> Result<()> {
+    fee_schedule.validate()?;
+    validate_governance_delay(governance_delay)?;
+    exposure_limits.validate()?;
+    dispute_policy.validate()?;
+
+    let config = &mut ctx.accounts.config;
+    config.authority = ctx.accounts.authority.key();
+    config.fee_schedule = fee_schedule;
+    config.creation_bond = creation_bond;
+    config.fee_tiers = Vec::new();
+    config.governance_delay = governance_delay;
+    config.exposure_limits = exposure_limits;
+    config.dispute_policy = dispute_policy;
+    config.pause = PauseState::default();
+
+    let granted_at = Clock::get()?.unix_timestamp;
+    let admin_role = &mut ctx.accounts.admin_role;
+    admin_role.role = Role::Admin;
+    admin_role.member = ctx.accounts.authority.key();
+    admin_role.granted_by = ctx.accounts.authority.key();
+    admin_role.granted_at = granted_at;
+
+    emit!(RoleGranted {
+        role: Role::Admin,
+        member: ctx.accounts.authority.key(),
+        granted_by: ctx.accounts.authority.key(),
+        timestamp: granted_at,
+    });
+
+    emit!(ConfigUpdated {
+        fee_schedule: ctx.accounts.config.fee_schedule,
+        creation_bond: ctx.accounts.config.creation_bond,
+        governance_delay: ctx.accounts.config.governance_delay,
+        exposure_limits: ctx.accounts.config.exposure_limits,
+        dispute_policy: ctx.accounts.config.dispute_policy,
+    });
+
+    Ok(())
+}
@@ -1,31 +1,57 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::MARKET_CREATION_AUTHORITY;
-use crate::constants::PRICE_FEED_CONFIG_PDA_SEED;
-use crate::state::PriceFeedConfig;
+use crate::constants::{MARKET_CREATION_AUTHORITY, PRICE_FEED_CONFIG_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::PriceFeedRegistered;
+use crate::state::{PriceFeedConfig, PriceFeedParams, Role, RoleAssignment};
 
 #[derive(Accounts)]
 #[instruction(feed: Pubkey)]
 pub struct InitializePriceFeed<'info> {
+    // checked before the registry entry so a non-admin gets a role error
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], payer.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
     #[account(
         init,
         payer = payer,
-        space = 8 + std::mem::size_of::<PriceFeedConfig>(),
+        space = 8 + PriceFeedConfig::INIT_SPACE,
         seeds = [
-          PRICE_FEED_CONFIG_PDA_SEED.as_bytes(), 
-          payer.key().as_ref(), 
-          feed.key().as_ref()
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            feed.key().as_ref()
         ],
         bump
     )]
     pub price_feed_config: Account<'info, PriceFeedConfig>,
-    #[account(mut, address = MARKET_CREATION_AUTHORITY)]
+    #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn initialize_price_feed(ctx: Context<InitializePriceFeed>, feed: Pubkey) -> Result<()> {
-    ctx.accounts.price_feed_config.price_feed = feed;
+pub fn initialize_price_feed(
+    ctx: Context<InitializePriceFeed>,
+    feed: Pubkey,
+    params: PriceFeedParams,
+) -> Result<()> {
+    let price_feed_config = &mut ctx.accounts.price_feed_config;
+
+    price_feed_config.version = PriceFeedConfig::VERSION;
+    price_feed_config.price_feed = feed;
+    price_feed_config.enabled = true;
+    price_feed_config.bump = ctx.bumps.price_feed_config;
+    price_feed_config.apply(params)?;
+
+    emit!(PriceFeedRegistered {
+        price_feed_config: price_feed_config.key(),
+        price_feed: feed,
+        symbol: price_feed_config.symbol.clone(),
+        decimals: price_feed_config.decimals,
+        oracle_source: price_feed_config.oracle_source,
+        max_staleness: price_feed_config.max_staleness,
+        max_confidence_bps: price_feed_config.max_confidence_bps,
+    });
 
     Ok(())
 }
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::{MARKET_CREATION_AUTHORITY, PRICE_FEED_CONFIG_PDA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::MarketMigrated;
+use crate::pda::{market_address, symbol_market_address};
+use crate::state::{Market, MarketV0, PriceFeedConfig, MARKET_VERSION_OFFSET};
+use crate::utils::{check_legacy_account, realloc_with_rent, versioned_account_error};
+
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    #[account(mut)]
+    /// CHECK: deserialized by hand since it may still have an old layout
+    pub market: UncheckedAccount<'info>,
+    /// The market's feed registration, which must be migrated first.
+    #[account(
+        mut,
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            price_feed_config.price_feed.as_ref()
+        ],
+        bump = price_feed_config.bump
+    )]
+    pub price_feed_config: Account<'info, PriceFeedConfig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades a market written by the first release to the current layout,
+/// resizing the account as needed and recording its bump. Anyone can pay for it.
+pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+    let market_info = ctx.accounts.market.to_account_info();
+    let data_len = check_legacy_account(&market_info, Market::DISCRIMINATOR)?;
+    let price_feed_config = &mut ctx.accounts.price_feed_config;
+
+    let mut market: Market = {
+        let data = market_info.try_borrow_data()?;
+        if data_len != MarketV0::SPACE {
+            return Err(versioned_account_error(
+                &data,
+                MARKET_VERSION_OFFSET,
+                Market::VERSION,
+                8 + std::mem::size_of::<Market>(),
+            ));
+        }
+        let legacy = MarketV0::deserialize(&mut &data[8..])?;
+        // the first release took the feed id from the creator unchecked,
+        // naming either the registration or the feed itself
+        if legacy.feed_id != price_feed_config.key().to_string()
+            && legacy.feed_id != price_feed_config.price_feed.to_string()
+        {
+            return Err(ErrorCode::PriceFeedMismatch.into());
+        }
+
+        let mut market = Market::try_from(legacy)?;
+        market.feed_id = price_feed_config.price_feed;
+        if !market.is_resolved() {
+            price_feed_config.open_markets += 1;
+        }
+        market
+    };
+
+    if market.feed_id != price_feed_config.price_feed {
+        return Err(ErrorCode::PriceFeedMismatch.into());
+    }
+
+    let (address, bump) = match market.symbol_seeded != 0 {
+        true => symbol_market_address(&market.authority, &market.symbol()),
+        false => market_address(&market.authority, market.market_id),
+    };
+    if address != market_info.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    }
+
+    market.version = Market::VERSION;
+    market.bump = bump;
+
+    let new_len = 8 + std::mem::size_of::<Market>();
+    realloc_with_rent(
+        &market_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_len,
+    )?;
+    market_info.try_borrow_mut_data()?[8..new_len].copy_from_slice(bytemuck::bytes_of(&market));
+
+    emit!(MarketMigrated {
+        market: market_info.key(),
+        version: market.version,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::{ACCOUNT_VERSION_OFFSET, USER_POSITION_PDA_SEED};
+use crate::events::PositionMigrated;
+use crate::state::{UserPosition, UserPositionV0};
+use crate::utils::{check_legacy_account, realloc_with_rent, versioned_account_error};
+
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    /// CHECK: only used to derive the position address, and may itself still
+    /// await migration
+    pub market: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            USER_POSITION_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    /// CHECK: deserialized by hand since it may still have an old layout
+    pub user_position: UncheckedAccount<'info>,
+    /// CHECK: only used to derive the position address
+    pub user: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades a position written by the first release to the current layout,
+/// growing the account as needed. Anyone can pay for it.
+pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+    let position_info = ctx.accounts.user_position.to_account_info();
+    let data_len = check_legacy_account(&position_info, UserPosition::DISCRIMINATOR)?;
+
+    let mut position: UserPosition = {
+        let data = position_info.try_borrow_data()?;
+        if data_len != UserPositionV0::SPACE {
+            return Err(versioned_account_error(
+                &data,
+                ACCOUNT_VERSION_OFFSET,
+                UserPosition::VERSION,
+                8 + UserPosition::INIT_SPACE,
+            ));
+        }
+        UserPositionV0::deserialize(&mut &data[8..])?.into()
+    };
+
+    position.version = UserPosition::VERSION;
+    position.bump = ctx.bumps.user_position;
+
+    let new_len = 8 + UserPosition::INIT_SPACE;
+    realloc_with_rent(
+        &position_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_len,
+    )?;
+    position.try_serialize(&mut &mut position_info.try_borrow_mut_data()?[..])?;
+
+    emit!(PositionMigrated {
+        user_position: position_info.key(),
+        market: position.market,
+        user: position.user,
+        deposited_yes: position.deposited_yes,
+        deposited_no: position.deposited_no,
+        version: position.version,
+    });
+
+    Ok(())
+}
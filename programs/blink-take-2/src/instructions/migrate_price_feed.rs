@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::ACCOUNT_VERSION_OFFSET;
+use crate::events::PriceFeedMigrated;
+use crate::pda::price_feed_config_address;
+use crate::state::{PriceFeedConfig, PriceFeedConfigV0};
+use crate::utils::{check_legacy_account, realloc_with_rent, versioned_account_error};
+
+#[derive(Accounts)]
+pub struct MigratePriceFeed<'info> {
+    #[account(mut)]
+    /// CHECK: deserialized by hand since it may still have an old layout
+    pub price_feed_config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades a price feed registry entry written by the first release to the
+/// current layout, recording its bump. Anyone can pay for it.
+pub fn migrate_price_feed(ctx: Context<MigratePriceFeed>) -> Result<()> {
+    let config_info = ctx.accounts.price_feed_config.to_account_info();
+    let data_len = check_legacy_account(&config_info, PriceFeedConfig::DISCRIMINATOR)?;
+
+    let mut price_feed_config: PriceFeedConfig = {
+        let data = config_info.try_borrow_data()?;
+        if data_len != PriceFeedConfigV0::SPACE {
+            return Err(versioned_account_error(
+                &data,
+                ACCOUNT_VERSION_OFFSET,
+                PriceFeedConfig::VERSION,
+                8 + PriceFeedConfig::INIT_SPACE,
+            ));
+        }
+        PriceFeedConfigV0::deserialize(&mut &data[8..])?.into()
+    };
+
+    let (address, bump) = price_feed_config_address(&price_feed_config.price_feed);
+    if address != config_info.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    }
+
+    price_feed_config.version = PriceFeedConfig::VERSION;
+    price_feed_config.bump = bump;
+
+    let new_len = 8 + PriceFeedConfig::INIT_SPACE;
+    realloc_with_rent(
+        &config_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_len,
+    )?;
+    price_feed_config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    emit!(PriceFeedMigrated {
+        price_feed_config: config_info.key(),
+        price_feed: price_feed_config.price_feed,
+        version: price_feed_config.version,
+    });
+
+    Ok(())
+}
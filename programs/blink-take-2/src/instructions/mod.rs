@@ -1,17 +1,73 @@
 pub mod cancel_bet;
+pub mod cancel_proposal;
+pub mod challenge_outcome;
+pub mod claim_creator_fee;
+pub mod claim_referral_rewards;
+pub mod claim_resolver_fee;
 pub mod claim_winnings;
+pub mod close_bet_receipt;
+pub mod close_price_feed;
 pub mod create_market;
 pub mod create_user;
+pub mod grant_role;
+pub mod init_config;
 pub mod init_price_feed;
+pub mod migrate_market;
+pub mod migrate_position;
+pub mod migrate_price_feed;
+pub mod pause_market;
+pub mod pause_program;
 pub mod place_bet;
+pub mod queue_proposal;
+pub mod refund_creator_bond;
 pub mod resolve_market;
+pub mod revoke_role;
+pub mod set_referral_code;
+pub mod settle_dispute;
+pub mod update_creation_bond;
+pub mod update_dispute_policy;
+pub mod update_exposure_limits;
+pub mod update_fee_schedule;
+pub mod update_fee_tiers;
+pub mod update_governance_delay;
+pub mod update_market_limits;
+pub mod update_price_feed;
+pub mod void_market;
 pub mod withdraw_team_fees;
 
 pub use cancel_bet::*;
+pub use cancel_proposal::*;
+pub use challenge_outcome::*;
+pub use claim_creator_fee::*;
+pub use claim_referral_rewards::*;
+pub use claim_resolver_fee::*;
 pub use claim_winnings::*;
+pub use close_bet_receipt::*;
+pub use close_price_feed::*;
 pub use create_market::*;
 pub use create_user::*;
+pub use grant_role::*;
+pub use init_config::*;
 pub use init_price_feed::*;
+pub use migrate_market::*;
+pub use migrate_position::*;
+pub use migrate_price_feed::*;
+pub use pause_market::*;
+pub use pause_program::*;
 pub use place_bet::*;
+pub use queue_proposal::*;
+pub use refund_creator_bond::*;
 pub use resolve_market::*;
+pub use revoke_role::*;
+pub use set_referral_code::*;
+pub use settle_dispute::*;
+pub use update_creation_bond::*;
+pub use update_dispute_policy::*;
+pub use update_exposure_limits::*;
+pub use update_fee_schedule::*;
+pub use update_fee_tiers::*;
+pub use update_governance_delay::*;
+pub use update_market_limits::*;
+pub use update_price_feed::*;
+pub use void_market::*;
 pub use withdraw_team_fees::*;
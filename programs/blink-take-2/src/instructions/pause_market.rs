@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_PDA_SEED, MARKET_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::{MarketPaused, MarketResumed};
+use crate::state::{Config, Market, PauseReason, PauseState, Role, RoleAssignment};
+
+#[derive(Accounts)]
+pub struct PauseMarket<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        seeds = [CONFIG_PDA_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Guardian as u8], guardian.key().as_ref()],
+        bump
+    )]
+    pub guardian_role: Account<'info, RoleAssignment>,
+    pub guardian: Signer<'info>,
+}
+
+pub fn pause_market(
+    ctx: Context<PauseMarket>,
+    reason: PauseReason,
+    allow_claims: bool,
+) -> Result<()> {
+    ctx.accounts.market.load_mut()?.set_pause(PauseState {
+        paused: true,
+        reason,
+        allow_claims,
+    });
+
+    emit!(MarketPaused {
+        market: ctx.accounts.market.key(),
+        guardian: ctx.accounts.guardian.key(),
+        reason,
+        allow_claims,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn resume_market(ctx: Context<PauseMarket>) -> Result<()> {
+    ctx.accounts
+        .market
+        .load_mut()?
+        .set_pause(PauseState::default());
+
+    emit!(MarketResumed {
+        market: ctx.accounts.market.key(),
+        guardian: ctx.accounts.guardian.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
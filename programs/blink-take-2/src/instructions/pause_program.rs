@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::{ProgramPaused, ProgramResumed};
+use crate::state::{Config, PauseReason, PauseState, Role, RoleAssignment};
+
+#[derive(Accounts)]
+pub struct PauseProgram<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_PDA_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Guardian as u8], guardian.key().as_ref()],
+        bump
+    )]
+    pub guardian_role: Account<'info, RoleAssignment>,
+    pub guardian: Signer<'info>,
+}
+
+pub fn pause_program(
+    ctx: Context<PauseProgram>,
+    reason: PauseReason,
+    allow_claims: bool,
+) -> Result<()> {
+    ctx.accounts.config.pause = PauseState {
+        paused: true,
+        reason,
+        allow_claims,
+    };
+
+    emit!(ProgramPaused {
+        guardian: ctx.accounts.guardian.key(),
+        reason,
+        allow_claims,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn resume_program(ctx: Context<PauseProgram>) -> Result<()> {
+    ctx.accounts.config.pause = PauseState::default();
+
+    emit!(ProgramResumed {
+        guardian: ctx.accounts.guardian.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,51 +1,91 @@
 use anchor_lang::prelude::*;
+use blink_core::pricing::calculate_shares;
+use blink_core::state::is_active;
 
 use crate::constants::{
-    MARKET_CREATION_AUTHORITY, MARKET_PDA_SEED, MIN_BET_AMOUNT, USER_POSITION_PDA_SEED,
+    BET_RECEIPT_PDA_SEED, CONFIG_PDA_SEED, MARKET_PDA_SEED, MIN_BET_AMOUNT, REFERRAL_PDA_SEED,
+    USER_POSITION_PDA_SEED, USER_PROFILE_PDA_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::state::{Market, UserPosition};
-use crate::utils::calculate_shares;
+use crate::events::BetPlaced;
+use crate::state::{BetReceipt, Config, Market, Referral, UserPosition, UserProfile};
 
 #[derive(Accounts)]
 pub struct PlaceBet<'info> {
     #[account(
-      mut,
-      seeds = [
-        MARKET_PDA_SEED.as_bytes(), 
-        MARKET_CREATION_AUTHORITY.as_ref(), 
-        market.memecoin_symbol.as_bytes()
-      ],
-      bump
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
     )]
-    pub market: Account<'info, Market>,
+    pub market: AccountLoader<'info, Market>,
     #[account(
         mut,
         seeds = [
-          USER_POSITION_PDA_SEED.as_bytes(), 
-          market.key().as_ref(), 
-          user.key().as_ref()
+            USER_POSITION_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
         ],
-        bump,
+        bump = user_position.bump,
         has_one = market,
         has_one = user
     )]
     pub user_position: Account<'info, UserPosition>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [USER_PROFILE_PDA_SEED.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [CONFIG_PDA_SEED.as_bytes()], bump)]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
+    /// CHECK: only recorded as the recipient of the referral rewards
+    pub referrer: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [REFERRAL_PDA_SEED.as_bytes(), user_position.key().as_ref()],
+        bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + BetReceipt::INIT_SPACE,
+        seeds = [
+            BET_RECEIPT_PDA_SEED.as_bytes(),
+            user_position.key().as_ref(),
+            user_position.bet_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bet_receipt: Option<Account<'info, BetReceipt>>,
 }
 
 pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, choice: bool) -> Result<()> {
-    let market = &mut ctx.accounts.market;
+    let mut market = ctx.accounts.market.load_mut()?;
     let user_position = &mut ctx.accounts.user_position;
 
     if amount < MIN_BET_AMOUNT {
         return Err(ErrorCode::BetAmountTooLow.into());
     }
 
-    let current_time = Clock::get()?.unix_timestamp as u64;
-    if current_time < market.start_time || current_time > market.start_time + market.duration {
+    ctx.accounts.config.check_not_paused(&market, false)?;
+
+    market.check_not_voided()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let current_time = current_timestamp as u64;
+    if !is_active(market.start_time, market.duration, current_time) {
         return Err(ErrorCode::MarketNotActive.into());
     }
 
@@ -53,16 +93,93 @@ pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, choice: bool) -> Result<()
         return Err(ErrorCode::InsufficientUserFunds.into());
     }
 
+    let mut referrer_key = None;
+    match ctx.accounts.referral.as_mut() {
+        Some(referral) => {
+            if !user_position.referred {
+                let referrer = ctx
+                    .accounts
+                    .referrer
+                    .as_ref()
+                    .ok_or(ErrorCode::ReferrerMissing)?;
+                if referrer.key() == ctx.accounts.user.key() {
+                    return Err(ErrorCode::SelfReferral.into());
+                }
+
+                referral.market = ctx.accounts.market.key();
+                referral.user_position = user_position.key();
+                referral.referrer = referrer.key();
+                referral.volume = 0;
+                referral.claimed = false;
+                user_position.referred = true;
+            } else if let Some(referrer) = ctx.accounts.referrer.as_ref() {
+                if referrer.key() != referral.referrer {
+                    return Err(ErrorCode::InvalidReferrer.into());
+                }
+            }
+
+            referrer_key = Some(referral.referrer);
+            referral.volume += amount;
+            market.referred_volume += amount;
+        }
+        None if user_position.referred => return Err(ErrorCode::ReferralAccountMissing.into()),
+        None => {}
+    }
+
     let shares = calculate_shares(amount);
+    let position = user_position.yes_shares + user_position.no_shares + shares;
+    market.check_exposure(shares, position, choice)?;
+
+    // a market counts as entered while the position holds a stake, so betting
+    // again after a cancellation enters it again
+    let entering = user_position.cost_basis() == 0;
 
     if choice {
         market.total_yes_shares += shares;
         user_position.yes_shares += shares;
+        user_position.deposited_yes += amount;
     } else {
         market.total_no_shares += shares;
         user_position.no_shares += shares;
+        user_position.deposited_no += amount;
     }
 
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.user = ctx.accounts.user.key();
+    user_profile.total_volume += amount;
+    if entering {
+        user_profile.markets_entered += 1;
+    }
+
+    // every bet takes a sequence number, whether or not a receipt is kept for it
+    let sequence = user_position.bet_count;
+    user_position.bet_count += 1;
+
+    if let Some(bet_receipt) = ctx.accounts.bet_receipt.as_mut() {
+        bet_receipt.market = ctx.accounts.market.key();
+        bet_receipt.user = ctx.accounts.user.key();
+        bet_receipt.sequence = sequence;
+        bet_receipt.outcome = choice;
+        bet_receipt.amount = amount;
+        bet_receipt.shares = shares;
+        bet_receipt.timestamp = current_timestamp;
+    }
+
+    emit!(BetPlaced {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.user.key(),
+        sequence,
+        choice,
+        amount,
+        shares,
+        referrer: referrer_key,
+        total_yes_shares: market.total_yes_shares,
+        total_no_shares: market.total_no_shares,
+        timestamp: current_timestamp,
+    });
+
+    // the transfer below needs the market's data unborrowed
+    drop(market);
 
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
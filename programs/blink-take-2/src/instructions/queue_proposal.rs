@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_PDA_SEED, PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::ProposalQueued;
+use crate::state::{Config, Proposal, ProposalAction, ProposalStatus, Role, RoleAssignment};
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct QueueProposal<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(seeds = [CONFIG_PDA_SEED.as_bytes()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue_proposal(
+    ctx: Context<QueueProposal>,
+    proposal_id: u64,
+    action: ProposalAction,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let queued_at = Clock::get()?.unix_timestamp;
+
+    proposal.proposal_id = proposal_id;
+    proposal.proposer = ctx.accounts.authority.key();
+    proposal.action = action;
+    proposal.status = ProposalStatus::Queued;
+    proposal.queued_at = queued_at;
+    // the delay is snapshotted so shortening it cannot fast-track queued proposals
+    proposal.executable_at = queued_at + ctx.accounts.config.governance_delay;
+
+    emit!(ProposalQueued {
+        proposal: proposal.key(),
+        proposal_id,
+        proposer: proposal.proposer,
+        action: proposal.action.clone(),
+        executable_at: proposal.executable_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MARKET_PDA_SEED;
+use crate::errors::ErrorCode;
+use crate::events::CreatorBondRefunded;
+use crate::state::Market;
+
+#[derive(Accounts)]
+pub struct RefundCreatorBond<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump,
+        has_one = authority
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn refund_creator_bond(ctx: Context<RefundCreatorBond>) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.check_settled()?;
+
+    // a slashed bond has already been moved out of the market
+    if market.creator_bond == 0 {
+        return Err(ErrorCode::CreatorBondAlreadySettled.into());
+    }
+
+    let creator_bond = market.creator_bond;
+
+    **ctx
+        .accounts
+        .market
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= creator_bond;
+    **ctx
+        .accounts
+        .authority
+        .to_account_info()
+        .try_borrow_mut_lamports()? += creator_bond;
+
+    market.creator_bond = 0;
+
+    emit!(CreatorBondRefunded {
+        market: ctx.accounts.market.key(),
+        creator: market.authority,
+        amount: creator_bond,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
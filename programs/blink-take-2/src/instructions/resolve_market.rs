@@ -1,58 +1,93 @@
 use anchor_lang::prelude::*;
+use blink_core::pricing::winning_outcome;
+use blink_core::state::is_expired;
 
-use crate::constants::{MARKET_CREATION_AUTHORITY, MARKET_PDA_SEED, PRICE_FEED_CONFIG_PDA_SEED};
+use crate::constants::{
+    CONFIG_PDA_SEED, MARKET_CREATION_AUTHORITY, MARKET_PDA_SEED, PRICE_FEED_CONFIG_PDA_SEED,
+    ROLE_PDA_SEED, TEAM_FEE_TIMELOCK,
+};
 use crate::errors::ErrorCode;
-use crate::state::{Market, PriceFeed, PriceFeedConfig};
+use crate::events::MarketResolved;
+use crate::state::{Config, Market, PriceFeed, PriceFeedConfig, Role, RoleAssignment};
 use crate::utils::fetch_pyth_price;
 
 #[derive(Accounts)]
 pub struct ResolveMarket<'info> {
     #[account(
-      mut,  
-      seeds = [
-        MARKET_PDA_SEED.as_bytes(), 
-        MARKET_CREATION_AUTHORITY.as_ref(), 
-        market.memecoin_symbol.as_bytes()
-      ],
-      bump,
-      has_one = authority
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
     )]
-    pub market: Account<'info, Market>,
-    #[account(address = MARKET_CREATION_AUTHORITY)]
+    pub market: AccountLoader<'info, Market>,
+    #[account(seeds = [CONFIG_PDA_SEED.as_bytes()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Resolver as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub resolver_role: Account<'info, RoleAssignment>,
     pub authority: Signer<'info>,
     #[account(
-      seeds = [
-        PRICE_FEED_CONFIG_PDA_SEED.as_bytes(), 
-        MARKET_CREATION_AUTHORITY.key().as_ref(), 
-        price_feed.key().as_ref()
-      ],
-      bump
+        mut,
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            price_feed.key().as_ref()
+        ],
+        bump = price_feed_config.bump
     )]
     pub price_feed_config: Account<'info, PriceFeedConfig>,
-    #[account(address = price_feed_config.price_feed)]
+    #[account(
+        address = price_feed_config.price_feed,
+        constraint = price_feed.key() == market.load()?.feed_id @ ErrorCode::PriceFeedMismatch
+    )]
     pub price_feed: Account<'info, PriceFeed>,
 }
 
 pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
-    let market = &mut ctx.accounts.market;
+    let market = &mut ctx.accounts.market.load_mut()?;
     let price_feed = &ctx.accounts.price_feed.to_account_info();
 
-    // let current_time = Clock::get()?.unix_timestamp as u64;
-    // if current_time <= market.start_time + market.duration {
-    //     return Err(ErrorCode::MarketNotExpired.into());
-    // }
+    ctx.accounts.config.check_not_paused(market, false)?;
 
-    if market.resolved {
-        return Err(ErrorCode::MarketAlreadyResolved.into());
+    let current_time = Clock::get()?.unix_timestamp;
+    if !is_expired(market.start_time, market.duration, current_time as u64) {
+        return Err(ErrorCode::MarketNotExpired.into());
     }
 
-    let final_price = fetch_pyth_price(price_feed).map_err(|_| ErrorCode::PriceFetchFailed)?;
+    let status = market.lifecycle().resolve().map_err(ErrorCode::from)?;
+
+    let final_price = fetch_pyth_price(price_feed, &ctx.accounts.price_feed_config)?;
+
+    let initial_price = market.initial_price;
+    let winning_outcome = winning_outcome(initial_price, final_price);
+
+    market.set_status(status.into());
+    market.set_winning_outcome(Some(winning_outcome));
+    market.final_price = final_price;
+    market.resolver = ctx.accounts.authority.key();
+
+    market.dispute_deadline = current_time + ctx.accounts.config.dispute_policy.window;
+    market.team_fee_unlock_time = current_time + TEAM_FEE_TIMELOCK;
 
-    let initial_price = market.initial_price.ok_or(ErrorCode::InitialPriceNotSet)?;
+    let price_feed_config = &mut ctx.accounts.price_feed_config;
+    price_feed_config.open_markets = price_feed_config.open_markets.saturating_sub(1);
 
-    market.resolved = true;
-    market.winning_outcome = Some(final_price > initial_price);
-    market.final_price = Some(final_price);
+    emit!(MarketResolved {
+        market: ctx.accounts.market.key(),
+        resolver: ctx.accounts.authority.key(),
+        initial_price,
+        final_price,
+        winning_outcome,
+        total_yes_shares: market.total_yes_shares,
+        total_no_shares: market.total_no_shares,
+        dispute_deadline: market.dispute_deadline,
+        timestamp: current_time,
+    });
 
     Ok(())
 }
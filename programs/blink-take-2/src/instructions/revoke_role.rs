@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::RoleRevoked;
+use crate::state::{execute_proposal, Proposal, ProposalAction, Role, RoleAssignment};
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            ROLE_PDA_SEED.as_bytes(),
+            &[role_assignment.role as u8],
+            role_assignment.member.as_ref()
+        ],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+    let role_assignment = &ctx.accounts.role_assignment;
+
+    // an admin revoking their own role could leave nobody able to manage roles
+    if role_assignment.key() == ctx.accounts.admin_role.key() {
+        return Err(ErrorCode::CannotRevokeOwnAdminRole.into());
+    }
+
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::RevokeRole {
+            role: role_assignment.role,
+            member: role_assignment.member,
+        },
+    )?;
+
+    emit!(RoleRevoked {
+        role: role_assignment.role,
+        member: role_assignment.member,
+        revoked_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
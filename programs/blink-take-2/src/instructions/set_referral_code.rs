@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_REFERRAL_CODE_LEN, USER_PROFILE_PDA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::ReferralCodeSet;
+use crate::state::UserProfile;
+
+#[derive(Accounts)]
+pub struct SetReferralCode<'info> {
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_PDA_SEED.as_bytes(), user.key().as_ref()],
+        bump,
+        has_one = user
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    pub user: Signer<'info>,
+}
+
+pub fn set_referral_code(ctx: Context<SetReferralCode>, referral_code: String) -> Result<()> {
+    if referral_code.is_empty()
+        || referral_code.len() > MAX_REFERRAL_CODE_LEN
+        || !referral_code.bytes().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err(ErrorCode::InvalidReferralCode.into());
+    }
+
+    ctx.accounts.user_profile.referral_code = referral_code;
+
+    emit!(ReferralCodeSet {
+        user: ctx.accounts.user.key(),
+        referral_code: ctx.accounts.user_profile.referral_code.clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{DISPUTE_PDA_SEED, MARKET_PDA_SEED, ROLE_PDA_SEED, TEAM_WALLET};
+use crate::events::DisputeSettled;
+use crate::state::{Dispute, Market, Role, RoleAssignment};
+
+#[derive(Accounts)]
+pub struct SettleDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [DISPUTE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump,
+        has_one = market,
+        has_one = challenger
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Arbiter as u8], arbiter.key().as_ref()],
+        bump
+    )]
+    pub arbiter_role: Account<'info, RoleAssignment>,
+    pub arbiter: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub challenger: UncheckedAccount<'info>,
+    #[account(mut, address = TEAM_WALLET)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub team_wallet: UncheckedAccount<'info>,
+}
+
+/// Upholding a challenge flips the outcome and makes the challenger the
+/// market's resolver, so they collect the resolver fee as their reward.
+/// Rejecting it slashes the bond to the team wallet. Either way the outcome
+/// is final afterwards and the dispute account's rent goes back to the
+/// challenger.
+pub fn settle_dispute(ctx: Context<SettleDispute>, uphold: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+    let dispute = &ctx.accounts.dispute;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let mut slashed_bond = 0;
+    if uphold {
+        market.set_winning_outcome(Some(dispute.proposed_outcome));
+        market.final_price = dispute.claimed_price;
+        market.resolver = dispute.challenger;
+    } else {
+        slashed_bond = dispute.bond;
+
+        **dispute.to_account_info().try_borrow_mut_lamports()? -= slashed_bond;
+        **ctx
+            .accounts
+            .team_wallet
+            .to_account_info()
+            .try_borrow_mut_lamports()? += slashed_bond;
+    }
+
+    market.disputed = 0;
+    market.dispute_deadline = current_time;
+
+    emit!(DisputeSettled {
+        market: ctx.accounts.market.key(),
+        dispute: dispute.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        upheld: uphold,
+        winning_outcome: market.winning_outcome().unwrap_or_default(),
+        refunded_bond: dispute.bond - slashed_bond,
+        slashed_bond,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_PDA_SEED, PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::ConfigUpdated;
+use crate::state::{
+    execute_proposal, Config, DisputePolicy, Proposal, ProposalAction, Role, RoleAssignment,
+};
+
+#[derive(Accounts)]
+pub struct UpdateDisputePolicy<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_PDA_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub authority: Signer<'info>,
+}
+
+pub fn update_dispute_policy(
+    ctx: Context<UpdateDisputePolicy>,
+    dispute_policy: DisputePolicy,
+) -> Result<()> {
+    dispute_policy.validate()?;
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::UpdateDisputePolicy { dispute_policy },
+    )?;
+
+    ctx.accounts.config.dispute_policy = dispute_policy;
+
+    emit!(ConfigUpdated {
+        fee_schedule: ctx.accounts.config.fee_schedule,
+        creation_bond: ctx.accounts.config.creation_bond,
+        governance_delay: ctx.accounts.config.governance_delay,
+        exposure_limits: ctx.accounts.config.exposure_limits,
+        dispute_policy: ctx.accounts.config.dispute_policy,
+    });
+
+    Ok(())
+}
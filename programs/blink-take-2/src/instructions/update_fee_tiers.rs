@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_PDA_SEED, PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::FeeTiersUpdated;
+use crate::state::{
+    execute_proposal, validate_fee_tiers, Config, FeeTier, Proposal, ProposalAction, Role,
+    RoleAssignment,
+};
+
+#[derive(Accounts)]
+pub struct UpdateFeeTiers<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_PDA_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub authority: Signer<'info>,
+}
+
+pub fn update_fee_tiers(ctx: Context<UpdateFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    validate_fee_tiers(&fee_tiers)?;
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::UpdateFeeTiers {
+            fee_tiers: fee_tiers.clone(),
+        },
+    )?;
+
+    ctx.accounts.config.fee_tiers = fee_tiers;
+
+    emit!(FeeTiersUpdated {
+        fee_tiers: ctx.accounts.config.fee_tiers.clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_PDA_SEED, PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::ConfigUpdated;
+use crate::state::{
+    execute_proposal, validate_governance_delay, Config, Proposal, ProposalAction, Role,
+    RoleAssignment,
+};
+
+#[derive(Accounts)]
+pub struct UpdateGovernanceDelay<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_PDA_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub authority: Signer<'info>,
+}
+
+pub fn update_governance_delay(
+    ctx: Context<UpdateGovernanceDelay>,
+    governance_delay: i64,
+) -> Result<()> {
+    validate_governance_delay(governance_delay)?;
+
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::UpdateGovernanceDelay { governance_delay },
+    )?;
+
+    ctx.accounts.config.governance_delay = governance_delay;
+
+    emit!(ConfigUpdated {
+        fee_schedule: ctx.accounts.config.fee_schedule,
+        creation_bond: ctx.accounts.config.creation_bond,
+        governance_delay: ctx.accounts.config.governance_delay,
+        exposure_limits: ctx.accounts.config.exposure_limits,
+        dispute_policy: ctx.accounts.config.dispute_policy,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MARKET_PDA_SEED, PROPOSAL_PDA_SEED, ROLE_PDA_SEED};
+use crate::events::MarketLimitsUpdated;
+use crate::state::{
+    execute_proposal, ExposureLimits, Market, Proposal, ProposalAction, Role, RoleAssignment,
+};
+
+#[derive(Accounts)]
+pub struct UpdateMarketLimits<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub authority: Signer<'info>,
+}
+
+/// Overrides the limits a market snapshotted from config at creation. Bets
+/// already placed are unaffected even if they exceed the new limits.
+pub fn update_market_limits(
+    ctx: Context<UpdateMarketLimits>,
+    exposure_limits: ExposureLimits,
+) -> Result<()> {
+    exposure_limits.validate()?;
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::UpdateMarketLimits {
+            market: ctx.accounts.market.key(),
+            exposure_limits,
+        },
+    )?;
+
+    ctx.accounts.market.load_mut()?.exposure_limits = exposure_limits;
+
+    emit!(MarketLimitsUpdated {
+        market: ctx.accounts.market.key(),
+        exposure_limits,
+    });
+
+    Ok(())
+}
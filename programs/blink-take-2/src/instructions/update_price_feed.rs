@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    MARKET_CREATION_AUTHORITY, PRICE_FEED_CONFIG_PDA_SEED, PROPOSAL_PDA_SEED, ROLE_PDA_SEED,
+};
+use crate::events::PriceFeedUpdated;
+use crate::state::{
+    execute_proposal, PriceFeedConfig, PriceFeedParams, Proposal, ProposalAction, Role,
+    RoleAssignment,
+};
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            price_feed_config.price_feed.as_ref()
+        ],
+        bump = price_feed_config.bump
+    )]
+    pub price_feed_config: Account<'info, PriceFeedConfig>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_PDA_SEED.as_bytes(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub authority: Signer<'info>,
+}
+
+/// Disabling a feed takes effect immediately so a misbehaving oracle can be
+/// pulled without waiting out the governance delay.
+#[derive(Accounts)]
+pub struct DisablePriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            price_feed_config.price_feed.as_ref()
+        ],
+        bump = price_feed_config.bump
+    )]
+    pub price_feed_config: Account<'info, PriceFeedConfig>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    pub authority: Signer<'info>,
+}
+
+pub fn update_price_feed(ctx: Context<UpdatePriceFeed>, params: PriceFeedParams) -> Result<()> {
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::UpdatePriceFeed {
+            price_feed: ctx.accounts.price_feed_config.price_feed,
+            params: params.clone(),
+        },
+    )?;
+
+    ctx.accounts.price_feed_config.apply(params)?;
+    emit_price_feed_updated(&ctx.accounts.price_feed_config);
+
+    Ok(())
+}
+
+/// Enabling goes through the timelock like any other change to the feed, and
+/// only a feed with a valid symbol and bounds can be enabled.
+pub fn enable_price_feed(ctx: Context<UpdatePriceFeed>) -> Result<()> {
+    execute_proposal(
+        &mut ctx.accounts.proposal,
+        ProposalAction::EnablePriceFeed {
+            price_feed: ctx.accounts.price_feed_config.price_feed,
+        },
+    )?;
+
+    ctx.accounts.price_feed_config.validate()?;
+    ctx.accounts.price_feed_config.enabled = true;
+    emit_price_feed_updated(&ctx.accounts.price_feed_config);
+
+    Ok(())
+}
+
+/// Stops new markets from being created on the feed. Existing markets can
+/// still be resolved against it.
+pub fn disable_price_feed(ctx: Context<DisablePriceFeed>) -> Result<()> {
+    ctx.accounts.price_feed_config.enabled = false;
+    emit_price_feed_updated(&ctx.accounts.price_feed_config);
+
+    Ok(())
+}
+
+fn emit_price_feed_updated(price_feed_config: &Account<PriceFeedConfig>) {
+    emit!(PriceFeedUpdated {
+        price_feed_config: price_feed_config.key(),
+        symbol: price_feed_config.symbol.clone(),
+        decimals: price_feed_config.decimals,
+        oracle_source: price_feed_config.oracle_source,
+        max_staleness: price_feed_config.max_staleness,
+        max_confidence_bps: price_feed_config.max_confidence_bps,
+        enabled: price_feed_config.enabled,
+    });
+}
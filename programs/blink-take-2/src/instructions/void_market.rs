@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CONFIG_PDA_SEED, MARKET_CREATION_AUTHORITY, MARKET_PDA_SEED, PRICE_FEED_CONFIG_PDA_SEED,
+    ROLE_PDA_SEED, TEAM_WALLET,
+};
+use crate::errors::ErrorCode;
+use crate::events::MarketVoided;
+use crate::state::{Config, Market, PriceFeedConfig, Role, RoleAssignment, VoidReason};
+
+#[derive(Accounts)]
+pub struct VoidMarket<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        seeds = [CONFIG_PDA_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.key().as_ref(),
+            market.load()?.feed_id.as_ref()
+        ],
+        bump = price_feed_config.bump
+    )]
+    pub price_feed_config: Account<'info, PriceFeedConfig>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Admin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    pub authority: Signer<'info>,
+    #[account(mut, address = TEAM_WALLET)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub team_wallet: UncheckedAccount<'info>,
+}
+
+pub fn void_market(ctx: Context<VoidMarket>, reason: VoidReason) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    let status = market.lifecycle().void().map_err(ErrorCode::from)?;
+
+    market.set_status(status.into());
+    market.set_winning_outcome(None);
+    market.set_void_reason(Some(reason));
+
+    let price_feed_config = &mut ctx.accounts.price_feed_config;
+    price_feed_config.open_markets = price_feed_config.open_markets.saturating_sub(1);
+
+    let mut slashed_bond = 0;
+    if reason == VoidReason::CreatorMisconfiguration {
+        slashed_bond = market.creator_bond;
+
+        **ctx
+            .accounts
+            .market
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= slashed_bond;
+        **ctx
+            .accounts
+            .team_wallet
+            .to_account_info()
+            .try_borrow_mut_lamports()? += slashed_bond;
+
+        market.creator_bond = 0;
+    }
+
+    emit!(MarketVoided {
+        market: ctx.accounts.market.key(),
+        reason,
+        slashed_bond,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
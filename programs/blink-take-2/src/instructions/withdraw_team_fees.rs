@@ -1,22 +1,41 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{TEAM_WALLET, MARKET_PDA_SEED, MARKET_CREATION_AUTHORITY};
+use crate::constants::{MARKET_PDA_SEED, ROLE_PDA_SEED, TEAM_WALLET};
 use crate::errors::ErrorCode;
-use crate::state::Market;
+use crate::events::TeamFeeWithdrawn;
+use crate::state::{Market, Role, RoleAssignment};
 
 #[derive(Accounts)]
 pub struct WithdrawTeamFee<'info> {
     #[account(
-      mut,
-      seeds = [
-        MARKET_PDA_SEED.as_bytes(), 
-        MARKET_CREATION_AUTHORITY.as_ref(), 
-        market.memecoin_symbol.as_bytes()
-      ],
-      bump, 
-      has_one = authority
+        mut,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            market.load()?.authority.as_ref(),
+            market.load()?.address_seed().as_ref()
+        ],
+        bump = market.load()?.bump
     )]
-    pub market: Account<'info, Market>,
+    pub market: AccountLoader<'info, Market>,
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Treasury as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub treasury_role: Account<'info, RoleAssignment>,
+    pub authority: Signer<'info>,
+    #[account(mut, address = TEAM_WALLET)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub team_wallet: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTeamFees<'info> {
+    #[account(
+        seeds = [ROLE_PDA_SEED.as_bytes(), &[Role::Treasury as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub treasury_role: Account<'info, RoleAssignment>,
     pub authority: Signer<'info>,
     #[account(mut, address = TEAM_WALLET)]
     /// CHECK: This is not dangerous because we don't read or write from this account
@@ -25,23 +44,70 @@ pub struct WithdrawTeamFee<'info> {
 }
 
 pub fn withdraw_team_fee(ctx: Context<WithdrawTeamFee>) -> Result<()> {
-    let market = &mut ctx.accounts.market;
     let current_time = Clock::get()?.unix_timestamp;
+    let team_fee = take_team_fee(&ctx.accounts.market, current_time)?;
 
-    if current_time < market.team_fee_unlock_time {
-        return Err(ErrorCode::TeamFeeTimelockNotExpired.into());
+    **ctx
+        .accounts
+        .team_wallet
+        .to_account_info()
+        .try_borrow_mut_lamports()? += team_fee;
+
+    Ok(())
+}
+
+/// Withdraws the team fee of every market passed in `remaining_accounts` in a
+/// single transfer to the team wallet.
+pub fn withdraw_team_fees<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawTeamFees<'info>>,
+) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
     }
 
-    let team_fee = market.to_account_info().lamports() * 5 / 100;
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut total_fees: u64 = 0;
+
+    for account in ctx.remaining_accounts {
+        let market = AccountLoader::<Market>::try_from(account)?;
+
+        total_fees += take_team_fee(&market, current_time)?;
+    }
 
-    **market.to_account_info().try_borrow_mut_lamports()? -= team_fee as u64;
     **ctx
         .accounts
         .team_wallet
         .to_account_info()
-        .try_borrow_mut_lamports()? += team_fee as u64;
-
-    market.team_fee_paid = true;
+        .try_borrow_mut_lamports()? += total_fees;
 
     Ok(())
 }
+
+/// Debits the protocol fee from a resolved market once its timelock has
+/// expired and marks it paid, returning the amount for the caller to credit.
+fn take_team_fee(market_loader: &AccountLoader<Market>, current_time: i64) -> Result<u64> {
+    let market = &mut market_loader.load_mut()?;
+
+    market.check_settled()?;
+
+    if market.team_fee_paid != 0 {
+        return Err(ErrorCode::FeeAlreadyClaimed.into());
+    }
+
+    if current_time < market.team_fee_unlock_time {
+        return Err(ErrorCode::TeamFeeTimelockNotExpired.into());
+    }
+
+    let team_fee = market.protocol_fee();
+
+    **market_loader.to_account_info().try_borrow_mut_lamports()? -= team_fee;
+    market.team_fee_paid = 1;
+
+    emit!(TeamFeeWithdrawn {
+        market: market_loader.key(),
+        amount: team_fee,
+        timestamp: current_time,
+    });
+
+    Ok(team_fee)
+}
@@ -2,11 +2,17 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod pda;
 pub mod state;
 pub mod utils;
 
 use crate::instructions::*;
+use crate::state::{
+    DisputePolicy, ExposureLimits, FeeSchedule, FeeTier, PauseReason, PriceFeedParams,
+    ProposalAction, Role, VoidReason,
+};
 
 declare_id!("HiXkrawYru9nPuyddUTD83xWjRb236r3NVLp67EeuGSw");
 
@@ -14,19 +20,30 @@ declare_id!("HiXkrawYru9nPuyddUTD83xWjRb236r3NVLp67EeuGSw");
 pub mod blink_take_2 {
     use super::*;
 
-    pub fn create_market(
-        ctx: Context<CreateMarket>,
-        memecoin_symbol: String,
-        feed_id: String,
-        duration: u64,
-    ) -> Result<()> {
-        instructions::create_market(ctx, memecoin_symbol, feed_id, duration)
+    pub fn create_market(ctx: Context<CreateMarket>, market_id: u64, duration: u64) -> Result<()> {
+        instructions::create_market(ctx, market_id, duration)
     }
 
     pub fn create_user(ctx: Context<CreateUser>) -> Result<()> {
         instructions::create_user(ctx)
     }
 
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        instructions::migrate_market(ctx)
+    }
+
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        instructions::migrate_position(ctx)
+    }
+
+    pub fn migrate_price_feed(ctx: Context<MigratePriceFeed>) -> Result<()> {
+        instructions::migrate_price_feed(ctx)
+    }
+
+    pub fn set_referral_code(ctx: Context<SetReferralCode>, referral_code: String) -> Result<()> {
+        instructions::set_referral_code(ctx, referral_code)
+    }
+
     pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, choice: bool) -> Result<()> {
         instructions::place_bet(ctx, amount, choice)
     }
@@ -43,11 +60,179 @@ pub mod blink_take_2 {
         instructions::withdraw_team_fee(ctx)
     }
 
+    pub fn withdraw_team_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawTeamFees<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_team_fees(ctx)
+    }
+
     pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
         instructions::cancel_bet(ctx)
     }
 
-    pub fn initialize_price_feed(ctx: Context<InitializePriceFeed>, feed: Pubkey) -> Result<()> {
-        instructions::initialize_price_feed(ctx, feed)
+    pub fn close_bet_receipt(ctx: Context<CloseBetReceipt>) -> Result<()> {
+        instructions::close_bet_receipt(ctx)
+    }
+
+    pub fn claim_creator_fee(ctx: Context<ClaimCreatorFee>) -> Result<()> {
+        instructions::claim_creator_fee(ctx)
+    }
+
+    pub fn claim_referral_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimReferralRewards<'info>>,
+    ) -> Result<()> {
+        instructions::claim_referral_rewards(ctx)
+    }
+
+    pub fn claim_resolver_fee(ctx: Context<ClaimResolverFee>) -> Result<()> {
+        instructions::claim_resolver_fee(ctx)
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_schedule: FeeSchedule,
+        creation_bond: u64,
+        governance_delay: i64,
+        exposure_limits: ExposureLimits,
+        dispute_policy: DisputePolicy,
+    ) -> Result<()> {
+        instructions::initialize_config(
+            ctx,
+            fee_schedule,
+            creation_bond,
+            governance_delay,
+            exposure_limits,
+            dispute_policy,
+        )
+    }
+
+    pub fn update_fee_schedule(
+        ctx: Context<UpdateFeeSchedule>,
+        fee_schedule: FeeSchedule,
+    ) -> Result<()> {
+        instructions::update_fee_schedule(ctx, fee_schedule)
+    }
+
+    pub fn update_fee_tiers(ctx: Context<UpdateFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::update_fee_tiers(ctx, fee_tiers)
+    }
+
+    pub fn update_creation_bond(
+        ctx: Context<UpdateCreationBond>,
+        creation_bond: u64,
+    ) -> Result<()> {
+        instructions::update_creation_bond(ctx, creation_bond)
+    }
+
+    pub fn update_governance_delay(
+        ctx: Context<UpdateGovernanceDelay>,
+        governance_delay: i64,
+    ) -> Result<()> {
+        instructions::update_governance_delay(ctx, governance_delay)
+    }
+
+    pub fn update_exposure_limits(
+        ctx: Context<UpdateExposureLimits>,
+        exposure_limits: ExposureLimits,
+    ) -> Result<()> {
+        instructions::update_exposure_limits(ctx, exposure_limits)
+    }
+
+    pub fn update_dispute_policy(
+        ctx: Context<UpdateDisputePolicy>,
+        dispute_policy: DisputePolicy,
+    ) -> Result<()> {
+        instructions::update_dispute_policy(ctx, dispute_policy)
+    }
+
+    pub fn update_market_limits(
+        ctx: Context<UpdateMarketLimits>,
+        exposure_limits: ExposureLimits,
+    ) -> Result<()> {
+        instructions::update_market_limits(ctx, exposure_limits)
+    }
+
+    pub fn queue_proposal(
+        ctx: Context<QueueProposal>,
+        proposal_id: u64,
+        action: ProposalAction,
+    ) -> Result<()> {
+        instructions::queue_proposal(ctx, proposal_id, action)
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        instructions::cancel_proposal(ctx)
+    }
+
+    pub fn challenge_outcome(ctx: Context<ChallengeOutcome>) -> Result<()> {
+        instructions::challenge_outcome(ctx)
+    }
+
+    pub fn settle_dispute(ctx: Context<SettleDispute>, uphold: bool) -> Result<()> {
+        instructions::settle_dispute(ctx, uphold)
+    }
+
+    pub fn void_market(ctx: Context<VoidMarket>, reason: VoidReason) -> Result<()> {
+        instructions::void_market(ctx, reason)
+    }
+
+    pub fn refund_creator_bond(ctx: Context<RefundCreatorBond>) -> Result<()> {
+        instructions::refund_creator_bond(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+        instructions::grant_role(ctx, role, member)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        instructions::revoke_role(ctx)
+    }
+
+    pub fn pause_program(
+        ctx: Context<PauseProgram>,
+        reason: PauseReason,
+        allow_claims: bool,
+    ) -> Result<()> {
+        instructions::pause_program(ctx, reason, allow_claims)
+    }
+
+    pub fn resume_program(ctx: Context<PauseProgram>) -> Result<()> {
+        instructions::resume_program(ctx)
+    }
+
+    pub fn pause_market(
+        ctx: Context<PauseMarket>,
+        reason: PauseReason,
+        allow_claims: bool,
+    ) -> Result<()> {
+        instructions::pause_market(ctx, reason, allow_claims)
+    }
+
+    pub fn resume_market(ctx: Context<PauseMarket>) -> Result<()> {
+        instructions::resume_market(ctx)
+    }
+
+    pub fn initialize_price_feed(
+        ctx: Context<InitializePriceFeed>,
+        feed: Pubkey,
+        params: PriceFeedParams,
+    ) -> Result<()> {
+        instructions::initialize_price_feed(ctx, feed, params)
+    }
+
+    pub fn update_price_feed(ctx: Context<UpdatePriceFeed>, params: PriceFeedParams) -> Result<()> {
+        instructions::update_price_feed(ctx, params)
+    }
+
+    pub fn enable_price_feed(ctx: Context<UpdatePriceFeed>) -> Result<()> {
+        instructions::enable_price_feed(ctx)
+    }
+
+    pub fn disable_price_feed(ctx: Context<DisablePriceFeed>) -> Result<()> {
+        instructions::disable_price_feed(ctx)
+    }
+
+    pub fn close_price_feed(ctx: Context<ClosePriceFeed>) -> Result<()> {
+        instructions::close_price_feed(ctx)
     }
 }
@@ -0,0 +1,109 @@
+//! Address derivation for every PDA the program owns. Account constraints
+//! spell out the same seeds, and accounts that store their bump are checked
+//! against it rather than re-derived.
+
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    BET_RECEIPT_PDA_SEED, CONFIG_PDA_SEED, DISPUTE_PDA_SEED, MARKET_CREATION_AUTHORITY,
+    MARKET_PDA_SEED, PRICE_FEED_CONFIG_PDA_SEED, PROPOSAL_PDA_SEED, REFERRAL_PDA_SEED,
+    ROLE_PDA_SEED, USER_POSITION_PDA_SEED, USER_PROFILE_PDA_SEED,
+};
+use crate::state::Role;
+
+pub fn config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_PDA_SEED.as_bytes()], &crate::ID)
+}
+
+pub fn market_address(authority: &Pubkey, market_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MARKET_PDA_SEED.as_bytes(),
+            authority.as_ref(),
+            market_id.to_le_bytes().as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Markets created before market ids were seeded by their symbol instead.
+pub fn symbol_market_address(authority: &Pubkey, symbol: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MARKET_PDA_SEED.as_bytes(),
+            authority.as_ref(),
+            symbol.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Price feeds are registered under the protocol's namespace rather than the
+/// admin who registered them.
+pub fn price_feed_config_address(price_feed: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PRICE_FEED_CONFIG_PDA_SEED.as_bytes(),
+            MARKET_CREATION_AUTHORITY.as_ref(),
+            price_feed.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn user_position_address(market: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            USER_POSITION_PDA_SEED.as_bytes(),
+            market.as_ref(),
+            user.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn referral_address(user_position: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFERRAL_PDA_SEED.as_bytes(), user_position.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn bet_receipt_address(user_position: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            BET_RECEIPT_PDA_SEED.as_bytes(),
+            user_position.as_ref(),
+            sequence.to_le_bytes().as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn user_profile_address(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[USER_PROFILE_PDA_SEED.as_bytes(), user.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn role_address(role: Role, member: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ROLE_PDA_SEED.as_bytes(), &[role as u8], member.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn proposal_address(proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROPOSAL_PDA_SEED.as_bytes(),
+            proposal_id.to_le_bytes().as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn dispute_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISPUTE_PDA_SEED.as_bytes(), market.as_ref()], &crate::ID)
+}
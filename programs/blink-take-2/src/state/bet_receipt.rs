@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct BetReceipt {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub sequence: u64,
+    pub outcome: bool,
+    pub amount: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use blink_core::fees::FeeRates;
+use bytemuck::{Pod, Zeroable};
+
+use crate::constants::{
+    BPS_DENOMINATOR, MAX_DISPUTE_WINDOW, MAX_FEE_TIERS, MAX_GOVERNANCE_DELAY, MAX_TOTAL_FEE_BPS,
+};
+use crate::errors::ErrorCode;
+use crate::state::{Market, PauseState};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub authority: Pubkey,
+    pub fee_schedule: FeeSchedule,
+    pub creation_bond: u64,
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>,
+    /// Seconds a queued proposal must wait before it can be executed.
+    pub governance_delay: i64,
+    pub exposure_limits: ExposureLimits,
+    pub dispute_policy: DisputePolicy,
+    pub pause: PauseState,
+}
+
+impl Config {
+    /// Fails if either the whole program or the given market is paused.
+    pub fn check_not_paused(&self, market: &Market, is_claim: bool) -> Result<()> {
+        if self.pause.is_blocking(is_claim) {
+            return Err(ErrorCode::ProgramPaused.into());
+        }
+
+        if market.pause().is_blocking(is_claim) {
+            return Err(ErrorCode::MarketPaused.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Fee split in basis points of a market's pool. A copy is snapshotted onto
+/// every market at creation so later config changes never affect live markets.
+#[derive(
+    AnchorSerialize,
+    AnchorDeserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    InitSpace,
+    Pod,
+    Zeroable,
+)]
+#[repr(C)]
+pub struct FeeSchedule {
+    pub protocol_bps: u16,
+    pub creator_bps: u16,
+    pub referrer_bps: u16,
+    pub resolver_bps: u16,
+}
+
+impl FeeSchedule {
+    pub fn total_bps(&self) -> u64 {
+        FeeRates::from(*self).total_bps()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.total_bps() > MAX_TOTAL_FEE_BPS || self.total_bps() > BPS_DENOMINATOR {
+            return Err(ErrorCode::InvalidFeeSchedule.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl From<FeeSchedule> for FeeRates {
+    fn from(schedule: FeeSchedule) -> Self {
+        Self {
+            protocol_bps: schedule.protocol_bps,
+            creator_bps: schedule.creator_bps,
+            referrer_bps: schedule.referrer_bps,
+            resolver_bps: schedule.resolver_bps,
+        }
+    }
+}
+
+/// Caps on how much can be bet into a market, snapshotted onto every market at
+/// creation like the fee schedule. A limit of zero means unlimited.
+#[derive(
+    AnchorSerialize,
+    AnchorDeserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    InitSpace,
+    Pod,
+    Zeroable,
+)]
+#[repr(C)]
+pub struct ExposureLimits {
+    pub max_bet_per_tx: u64,
+    /// Maximum shares a single user can hold across both sides of a market.
+    pub max_position_per_user: u64,
+    pub max_market_tvl: u64,
+    /// Maximum size of the larger side relative to the smaller one, in basis
+    /// points (e.g. 50_000 lets one side grow to five times the other).
+    pub max_imbalance_bps: u64,
+}
+
+impl ExposureLimits {
+    pub fn validate(&self) -> Result<()> {
+        // a ratio below 1x could never be satisfied by both sides at once
+        if self.max_imbalance_bps != 0 && self.max_imbalance_bps < BPS_DENOMINATOR {
+            return Err(ErrorCode::InvalidExposureLimits.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// How long a resolution can be challenged for, and the bond a challenger
+/// must post. A zero window makes resolutions final immediately.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct DisputePolicy {
+    pub window: i64,
+    pub bond: u64,
+}
+
+impl DisputePolicy {
+    pub fn validate(&self) -> Result<()> {
+        if !(0..=MAX_DISPUTE_WINDOW).contains(&self.window) {
+            return Err(ErrorCode::InvalidDisputePolicy.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Bettors whose settled volume reaches `min_volume` get `discount_bps` of
+/// the fee on their winnings rebated at claim time.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct FeeTier {
+    pub min_volume: u64,
+    pub discount_bps: u16,
+}
+
+pub fn validate_fee_tiers(fee_tiers: &[FeeTier]) -> Result<()> {
+    if fee_tiers.len() > MAX_FEE_TIERS {
+        return Err(ErrorCode::InvalidFeeTiers.into());
+    }
+
+    if fee_tiers
+        .iter()
+        .any(|tier| tier.discount_bps as u64 > BPS_DENOMINATOR)
+    {
+        return Err(ErrorCode::InvalidFeeTiers.into());
+    }
+
+    // tiers must be sorted by strictly increasing volume so lookups can stop early
+    if fee_tiers
+        .windows(2)
+        .any(|pair| pair[0].min_volume >= pair[1].min_volume)
+    {
+        return Err(ErrorCode::InvalidFeeTiers.into());
+    }
+
+    Ok(())
+}
+
+pub fn validate_governance_delay(governance_delay: i64) -> Result<()> {
+    if !(0..=MAX_GOVERNANCE_DELAY).contains(&governance_delay) {
+        return Err(ErrorCode::InvalidGovernanceDelay.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// A bonded challenge against a market's resolution, opened during its dispute
+/// window and settled by an arbiter. The bond is held in this account.
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub market: Pubkey,
+    pub challenger: Pubkey,
+    /// Price the market's feed reported when the challenge was opened.
+    pub claimed_price: i64,
+    pub proposed_outcome: bool,
+    pub bond: u64,
+    pub opened_at: i64,
+}
@@ -0,0 +1,150 @@
+//! The first release's account layouts, only read by the `migrate_*`
+//! instructions. Those accounts carry no `version` byte and are told apart by
+//! their allocated size. Every versioned layout shipped at once, at the current
+//! versions, so there are no intermediate layouts to read.
+//!
+//! Conversions leave `bump` zeroed for the migration to fill in.
+
+use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+
+use crate::constants::{BPS_DENOMINATOR, MAX_SYMBOL_LEN, STALENESS_THRESHOLD};
+use crate::errors::ErrorCode;
+use crate::state::{
+    FeeSchedule, Market, MarketStatus, OracleSource, PriceFeedConfig, UserPosition,
+};
+use crate::utils::symbol_to_bytes;
+
+/// Markets as the first release wrote them: Borsh-serialized, addressed by
+/// their symbol and allocated with `std::mem::size_of`, so the account is
+/// larger than the serialized data.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MarketV0 {
+    pub memecoin_symbol: String,
+    /// The market's price feed registration, or the feed itself, in base58.
+    pub feed_id: String,
+    pub start_time: u64,
+    pub duration: u64,
+    pub total_yes_shares: u64,
+    pub total_no_shares: u64,
+    pub resolved: bool,
+    pub winning_outcome: Option<bool>,
+    pub authority: Pubkey,
+    pub initial_price: Option<i64>,
+    pub final_price: Option<i64>,
+    pub team_fee_paid: bool,
+    pub team_fee_unlock_time: i64,
+}
+
+impl MarketV0 {
+    pub const SPACE: usize = 8 + std::mem::size_of::<MarketV0>();
+}
+
+impl TryFrom<MarketV0> for Market {
+    type Error = Error;
+
+    /// Leaves `feed_id` for the migration to fill in from the feed's
+    /// registration. The first release kept a flat 5% for the team, which
+    /// carries over as the protocol fee.
+    fn try_from(market: MarketV0) -> Result<Self> {
+        if market.memecoin_symbol.len() > MAX_SYMBOL_LEN || market.memecoin_symbol.contains('\0') {
+            return Err(ErrorCode::InvalidSymbol.into());
+        }
+
+        let mut upgraded = Market::zeroed();
+        upgraded.version = Market::VERSION;
+        upgraded.set_status(match market.resolved {
+            true => MarketStatus::Resolved,
+            false => MarketStatus::Open,
+        });
+        upgraded.set_winning_outcome(market.winning_outcome);
+        upgraded.team_fee_paid = market.team_fee_paid as u8;
+        upgraded.symbol_seeded = 1;
+        upgraded.authority = market.authority;
+        upgraded.memecoin_symbol = symbol_to_bytes(&market.memecoin_symbol);
+        upgraded.start_time = market.start_time;
+        upgraded.duration = market.duration;
+        upgraded.total_yes_shares = market.total_yes_shares;
+        upgraded.total_no_shares = market.total_no_shares;
+        upgraded.initial_price = market.initial_price.unwrap_or_default();
+        upgraded.final_price = market.final_price.unwrap_or_default();
+        upgraded.team_fee_unlock_time = market.team_fee_unlock_time;
+        upgraded.fee_schedule = FeeSchedule {
+            protocol_bps: 500,
+            creator_bps: 0,
+            referrer_bps: 0,
+            resolver_bps: 0,
+        };
+        Ok(upgraded)
+    }
+}
+
+/// Price feed registrations as the first release wrote them, holding only
+/// the feed.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PriceFeedConfigV0 {
+    pub price_feed: Pubkey,
+}
+
+impl PriceFeedConfigV0 {
+    pub const SPACE: usize = 8 + std::mem::size_of::<PriceFeedConfigV0>();
+}
+
+impl From<PriceFeedConfigV0> for PriceFeedConfig {
+    /// The first release checked neither staleness nor confidence, and kept
+    /// no symbol, so the feed comes back disabled until an admin sets them.
+    fn from(config: PriceFeedConfigV0) -> Self {
+        Self {
+            version: PriceFeedConfig::VERSION,
+            price_feed: config.price_feed,
+            symbol: String::new(),
+            decimals: 0,
+            oracle_source: OracleSource::Pyth,
+            max_staleness: STALENESS_THRESHOLD,
+            max_confidence_bps: BPS_DENOMINATOR as u16,
+            enabled: false,
+            open_markets: 0,
+            bump: 0,
+            reserved: [0; 23],
+        }
+    }
+}
+
+/// Positions as the first release wrote them. Like markets they were
+/// allocated with `std::mem::size_of`, so the account includes struct padding.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserPositionV0 {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    pub claimed: bool,
+}
+
+impl UserPositionV0 {
+    pub const SPACE: usize = 8 + std::mem::size_of::<UserPositionV0>();
+}
+
+impl From<UserPositionV0> for UserPosition {
+    /// Shares were minted one per lamport at the time, so they are also the
+    /// cost basis of the open position. Referrals and bet counts did not
+    /// exist yet.
+    fn from(position: UserPositionV0) -> Self {
+        Self {
+            version: UserPosition::VERSION,
+            market: position.market,
+            user: position.user,
+            yes_shares: position.yes_shares,
+            no_shares: position.no_shares,
+            claimed: position.claimed,
+            referred: false,
+            bet_count: 0,
+            deposited_yes: position.yes_shares,
+            deposited_no: position.no_shares,
+            refunded: 0,
+            claimed_amount: 0,
+            bump: 0,
+            reserved: [0; 31],
+        }
+    }
+}
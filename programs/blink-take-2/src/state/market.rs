@@ -1,18 +1,297 @@
 use anchor_lang::prelude::*;
+use blink_core::fees::{calculate_fees, FeeBreakdown};
+use blink_core::state as lifecycle;
 
-#[account]
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::ErrorCode;
+use crate::state::{ExposureLimits, FeeSchedule, PauseReason, PauseState};
+
+/// Offsets of `Market` fields within the account, including the 8-byte
+/// discriminator, for `getProgramAccounts` memcmp filters.
+pub const MARKET_VERSION_OFFSET: usize = 8;
+pub const MARKET_STATUS_OFFSET: usize = 9;
+pub const MARKET_AUTHORITY_OFFSET: usize = 16;
+pub const MARKET_FEED_ID_OFFSET: usize = 48;
+pub const MARKET_SYMBOL_OFFSET: usize = 80;
+pub const MARKET_ID_OFFSET: usize = 96;
+pub const MARKET_START_TIME_OFFSET: usize = 104;
+pub const MARKET_DURATION_OFFSET: usize = 112;
+
+// Clients hardcode the offsets above, so moving a field must not go unnoticed.
+const _: () = {
+    use std::mem::offset_of;
+    assert!(MARKET_VERSION_OFFSET == 8 + offset_of!(Market, version));
+    assert!(MARKET_STATUS_OFFSET == 8 + offset_of!(Market, status));
+    assert!(MARKET_AUTHORITY_OFFSET == 8 + offset_of!(Market, authority));
+    assert!(MARKET_FEED_ID_OFFSET == 8 + offset_of!(Market, feed_id));
+    assert!(MARKET_SYMBOL_OFFSET == 8 + offset_of!(Market, memecoin_symbol));
+    assert!(MARKET_ID_OFFSET == 8 + offset_of!(Market, market_id));
+    assert!(MARKET_START_TIME_OFFSET == 8 + offset_of!(Market, start_time));
+    assert!(MARKET_DURATION_OFFSET == 8 + offset_of!(Market, duration));
+};
+
+/// Loaded zero-copy so the hot paths skip Borsh. Fields are ordered to leave
+/// no padding, and values without a plain-old-data form are stored as bytes
+/// behind accessors: see `status`, `winning_outcome`, `void_reason`,
+/// `resolver` and `pause`. Boolean flags are 0 or 1.
+#[account(zero_copy)]
 pub struct Market {
-    pub memecoin_symbol: String,
-    pub feed_id: String,
+    pub version: u8,
+    pub status: u8,
+    pub winning_outcome: u8,
+    pub void_reason: u8,
+    pub team_fee_paid: u8,
+    pub creator_fee_paid: u8,
+    pub resolver_fee_paid: u8,
+    pub disputed: u8,
+    pub authority: Pubkey,
+    pub feed_id: Pubkey,
+    /// Zero-padded, see `Market::symbol`.
+    pub memecoin_symbol: [u8; 16],
+    pub market_id: u64,
     pub start_time: u64,
     pub duration: u64,
     pub total_yes_shares: u64,
     pub total_no_shares: u64,
-    pub resolved: bool,
-    pub winning_outcome: Option<bool>,
-    pub authority: Pubkey,
-    pub initial_price: Option<i64>,
-    pub final_price: Option<i64>,
-    pub team_fee_paid: bool,
+    pub initial_price: i64,
+    /// Only meaningful once the market is resolved.
+    pub final_price: i64,
     pub team_fee_unlock_time: i64,
+    /// The default key until the market is resolved.
+    pub resolver: Pubkey,
+    pub referred_volume: u64,
+    pub creator_bond: u64,
+    pub fee_rebates: u64,
+    pub exposure_limits: ExposureLimits,
+    /// Resolutions can be challenged until this time.
+    pub dispute_deadline: i64,
+    pub fee_schedule: FeeSchedule,
+    pub paused: u8,
+    pub pause_reason: u8,
+    pub allow_claims_while_paused: u8,
+    pub bump: u8,
+    /// 1 for markets created before market ids, whose address is seeded by
+    /// the symbol instead. See `Market::address_seed`.
+    pub symbol_seeded: u8,
+    pub padding: [u8; 3],
+    /// The part of cancelled stakes that was not refunded. It stays in the
+    /// market and is paid out with the pool.
+    pub cancellation_penalties: u64,
+    /// Room for new fields without another realloc.
+    pub reserved: [u8; 56],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum MarketStatus {
+    Open,
+    Resolved,
+    Voided,
+}
+
+impl From<lifecycle::MarketStatus> for MarketStatus {
+    fn from(status: lifecycle::MarketStatus) -> Self {
+        match status {
+            lifecycle::MarketStatus::Open => MarketStatus::Open,
+            lifecycle::MarketStatus::Resolved => MarketStatus::Resolved,
+            lifecycle::MarketStatus::Voided => MarketStatus::Voided,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum VoidReason {
+    /// The creator set the market up incorrectly; their bond is slashed.
+    CreatorMisconfiguration,
+    /// The oracle could not provide a usable price to settle the market.
+    OracleFailure,
+}
+
+impl Market {
+    pub const VERSION: u8 = 4;
+
+    pub fn status(&self) -> MarketStatus {
+        self.lifecycle().into()
+    }
+
+    /// The status as seen by the lifecycle rules in `blink_core`.
+    pub fn lifecycle(&self) -> lifecycle::MarketStatus {
+        lifecycle::MarketStatus::from_u8(self.status)
+    }
+
+    pub fn set_status(&mut self, status: MarketStatus) {
+        self.status = status as u8;
+    }
+
+    pub fn winning_outcome(&self) -> Option<bool> {
+        match self.winning_outcome {
+            0 => None,
+            outcome => Some(outcome == 2),
+        }
+    }
+
+    pub fn set_winning_outcome(&mut self, outcome: Option<bool>) {
+        self.winning_outcome = match outcome {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        };
+    }
+
+    pub fn void_reason(&self) -> Option<VoidReason> {
+        match self.void_reason {
+            0 => None,
+            1 => Some(VoidReason::CreatorMisconfiguration),
+            _ => Some(VoidReason::OracleFailure),
+        }
+    }
+
+    pub fn set_void_reason(&mut self, reason: Option<VoidReason>) {
+        self.void_reason = reason.map_or(0, |reason| reason as u8 + 1);
+    }
+
+    pub fn resolver(&self) -> Option<Pubkey> {
+        (self.resolver != Pubkey::default()).then_some(self.resolver)
+    }
+
+    pub fn pause(&self) -> PauseState {
+        PauseState {
+            paused: self.paused != 0,
+            reason: match self.pause_reason {
+                0 => PauseReason::None,
+                1 => PauseReason::BugDiscovered,
+                2 => PauseReason::OracleManipulation,
+                3 => PauseReason::Maintenance,
+                _ => PauseReason::Other,
+            },
+            allow_claims: self.allow_claims_while_paused != 0,
+        }
+    }
+
+    pub fn set_pause(&mut self, pause: PauseState) {
+        self.paused = pause.paused as u8;
+        self.pause_reason = pause.reason as u8;
+        self.allow_claims_while_paused = pause.allow_claims as u8;
+    }
+
+    pub fn symbol(&self) -> String {
+        let len = self
+            .memecoin_symbol
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(self.memecoin_symbol.len());
+        String::from_utf8_lossy(&self.memecoin_symbol[..len]).into_owned()
+    }
+
+    /// The last seed of the market's address.
+    pub fn address_seed(&self) -> Vec<u8> {
+        match self.symbol_seeded != 0 {
+            true => self.symbol().into_bytes(),
+            false => self.market_id.to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Resolved and voided markets are both settled.
+    pub fn is_resolved(&self) -> bool {
+        self.lifecycle().is_settled()
+    }
+
+    /// Bets are accepted until the market is voided.
+    pub fn check_not_voided(&self) -> Result<()> {
+        self.lifecycle()
+            .check_not_voided()
+            .map_err(ErrorCode::from)?;
+
+        Ok(())
+    }
+
+    /// Cancellations are only accepted while the market is open.
+    pub fn check_open(&self) -> Result<()> {
+        self.lifecycle().check_open().map_err(ErrorCode::from)?;
+
+        Ok(())
+    }
+
+    pub fn check_settled(&self) -> Result<()> {
+        self.lifecycle().check_settled().map_err(ErrorCode::from)?;
+
+        Ok(())
+    }
+
+    /// Shares are lamport-denominated, so the pool is the sum of both sides
+    /// plus what cancellations left behind.
+    pub fn total_pool(&self) -> u64 {
+        self.total_yes_shares + self.total_no_shares + self.cancellation_penalties
+    }
+
+    /// Tier discounts are rebated out of the protocol's share of the fees.
+    /// Voided markets have no winners to pay cancellation penalties to, so
+    /// those go to the protocol instead.
+    pub fn protocol_fee(&self) -> u64 {
+        if self.is_voided() {
+            return self.cancellation_penalties;
+        }
+
+        self.fees().protocol.saturating_sub(self.fee_rebates)
+    }
+
+    /// Checks a bet adding `shares` to `choice` against the market's exposure
+    /// limits, where `position` is the bettor's total shares including it.
+    pub fn check_exposure(&self, shares: u64, position: u64, choice: bool) -> Result<()> {
+        let limits = &self.exposure_limits;
+
+        if limits.max_bet_per_tx != 0 && shares > limits.max_bet_per_tx {
+            return Err(ErrorCode::BetTooLarge.into());
+        }
+
+        if limits.max_position_per_user != 0 && position > limits.max_position_per_user {
+            return Err(ErrorCode::PositionLimitExceeded.into());
+        }
+
+        if limits.max_market_tvl != 0 && self.total_pool() + shares > limits.max_market_tvl {
+            return Err(ErrorCode::MarketTvlLimitExceeded.into());
+        }
+
+        let (side, other) = if choice {
+            (self.total_yes_shares + shares, self.total_no_shares)
+        } else {
+            (self.total_no_shares + shares, self.total_yes_shares)
+        };
+
+        // an empty opposite side is not checked so the first bets can open the market
+        if limits.max_imbalance_bps != 0
+            && other != 0
+            && side as u128 * BPS_DENOMINATOR as u128
+                > other as u128 * limits.max_imbalance_bps as u128
+        {
+            return Err(ErrorCode::MarketImbalanceLimitExceeded.into());
+        }
+
+        Ok(())
+    }
+
+    /// Fails while the outcome can still change, i.e. during the dispute
+    /// window or while a challenge awaits settlement.
+    pub fn check_outcome_final(&self, current_time: i64) -> Result<()> {
+        lifecycle::check_outcome_final(self.disputed != 0, self.dispute_deadline, current_time)
+            .map_err(ErrorCode::from)?;
+
+        Ok(())
+    }
+
+    pub fn is_voided(&self) -> bool {
+        self.lifecycle() == lifecycle::MarketStatus::Voided
+    }
+
+    /// Voided markets refund every bettor in full, so no fees are owed on them.
+    pub fn fees(&self) -> FeeBreakdown {
+        if self.is_voided() {
+            return FeeBreakdown::default();
+        }
+
+        calculate_fees(
+            self.total_pool(),
+            self.referred_volume,
+            &self.fee_schedule.into(),
+        )
+    }
 }
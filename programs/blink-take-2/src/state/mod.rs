@@ -1,7 +1,25 @@
+pub mod bet_receipt;
+pub mod config;
+pub mod dispute;
+pub mod legacy;
 pub mod market;
+pub mod pause;
 pub mod price_feed;
+pub mod proposal;
+pub mod referral;
+pub mod role;
 pub mod user_position;
+pub mod user_profile;
 
+pub use bet_receipt::*;
+pub use config::*;
+pub use dispute::*;
+pub use legacy::*;
 pub use market::*;
+pub use pause::*;
 pub use price_feed::*;
+pub use proposal::*;
+pub use referral::*;
+pub use role::*;
 pub use user_position::*;
+pub use user_profile::*;
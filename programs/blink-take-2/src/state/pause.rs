@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct PauseState {
+    pub paused: bool,
+    pub reason: PauseReason,
+    /// Lets bettors keep claiming winnings while everything else is halted.
+    pub allow_claims: bool,
+}
+
+impl PauseState {
+    pub fn is_blocking(&self, is_claim: bool) -> bool {
+        self.paused && !(is_claim && self.allow_claims)
+    }
+}
+
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub enum PauseReason {
+    #[default]
+    None,
+    BugDiscovered,
+    OracleManipulation,
+    Maintenance,
+    Other,
+}
@@ -1,25 +1,105 @@
-use std::{ops::Deref, str::FromStr};
+use std::ops::Deref;
 
 use anchor_lang::prelude::*;
 use pyth_sdk_solana::state::{load_price_account, SolanaPriceAccount};
 
+use crate::constants::{BPS_DENOMINATOR, MAX_SYMBOL_LEN};
 use crate::errors::ErrorCode;
+use crate::utils::validate_symbol;
 
 #[account]
+#[derive(InitSpace)]
 pub struct PriceFeedConfig {
+    pub version: u8,
     pub price_feed: Pubkey,
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    pub decimals: u8,
+    pub oracle_source: OracleSource,
+    pub max_staleness: u64,
+    pub max_confidence_bps: u16,
+    pub enabled: bool,
+    /// Markets on this feed that are not settled yet. The feed cannot be
+    /// closed while any remain, since they resolve against it.
+    pub open_markets: u64,
+    pub bump: u8,
+    /// Room for new fields without another realloc.
+    pub reserved: [u8; 23],
+}
+
+impl PriceFeedConfig {
+    pub const VERSION: u8 = 2;
+
+    pub fn apply(&mut self, params: PriceFeedParams) -> Result<()> {
+        validate_params(
+            &params.symbol,
+            params.max_staleness,
+            params.max_confidence_bps,
+        )?;
+
+        self.symbol = params.symbol;
+        self.decimals = params.decimals;
+        self.oracle_source = params.oracle_source;
+        self.max_staleness = params.max_staleness;
+        self.max_confidence_bps = params.max_confidence_bps;
+
+        Ok(())
+    }
+
+    /// Checks the feed has what markets need to resolve against it, which a
+    /// feed migrated from the first release lacks until it is updated.
+    pub fn validate(&self) -> Result<()> {
+        validate_params(&self.symbol, self.max_staleness, self.max_confidence_bps)
+    }
+}
+
+fn validate_params(symbol: &str, max_staleness: u64, max_confidence_bps: u16) -> Result<()> {
+    validate_symbol(symbol)?;
+
+    if max_staleness == 0 {
+        return Err(ErrorCode::InvalidPriceFeedParams.into());
+    }
+
+    if max_confidence_bps == 0 || max_confidence_bps as u64 > BPS_DENOMINATOR {
+        return Err(ErrorCode::InvalidPriceFeedParams.into());
+    }
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct PriceFeedParams {
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    pub decimals: u8,
+    pub oracle_source: OracleSource,
+    /// Maximum age of a price, in seconds, before it is rejected as stale.
+    pub max_staleness: u64,
+    /// Maximum confidence interval, in basis points of the price.
+    pub max_confidence_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum OracleSource {
+    Pyth,
 }
 
 #[derive(Clone)]
 pub struct PriceFeed(pyth_sdk_solana::PriceFeed);
 
 impl anchor_lang::Owner for PriceFeed {
+    #[cfg(feature = "mock-oracle")]
+    fn owner() -> Pubkey {
+        mock_oracle::ID
+    }
+
+    #[cfg(not(feature = "mock-oracle"))]
     fn owner() -> Pubkey {
         // https://docs.pyth.network/price-feeds/contract-addresses/solana#legacy-oracle
         // pyth devnet oracle address = gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s
         // pyth mainnet oracle address = FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH
         let oracle_addr = "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s";
-        return Pubkey::from_str(&oracle_addr).unwrap();
+        oracle_addr.parse().unwrap()
     }
 }
 
@@ -31,7 +111,7 @@ impl anchor_lang::AccountDeserialize for PriceFeed {
         let zeros: [u8; 32] = [0; 32];
         let dummy_key = solana_program::pubkey::Pubkey::from(zeros);
         let feed = account.to_price_feed(&dummy_key);
-        return Ok(PriceFeed(feed));
+        Ok(PriceFeed(feed))
     }
 }
 
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_FEE_TIERS;
+use crate::errors::ErrorCode;
+use crate::events::ProposalExecuted;
+use crate::state::{DisputePolicy, ExposureLimits, FeeSchedule, FeeTier, PriceFeedParams, Role};
+
+/// A queued admin mutation. It can only be carried out by the matching admin
+/// instruction once `executable_at` has passed, and can be cancelled until then.
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub status: ProposalStatus,
+    pub queued_at: i64,
+    pub executable_at: i64,
+}
+
+impl Proposal {
+    /// Marks the proposal as executed if it is queued, past its timelock and
+    /// describes exactly the mutation being applied.
+    pub fn execute(&mut self, action: &ProposalAction, current_time: i64) -> Result<()> {
+        if self.status != ProposalStatus::Queued {
+            return Err(ErrorCode::ProposalNotQueued.into());
+        }
+
+        if current_time < self.executable_at {
+            return Err(ErrorCode::ProposalTimelockNotExpired.into());
+        }
+
+        if self.action != *action {
+            return Err(ErrorCode::ProposalActionMismatch.into());
+        }
+
+        self.status = ProposalStatus::Executed;
+
+        Ok(())
+    }
+}
+
+/// Consumes `proposal` for `action`, failing if it is not ready or describes a
+/// different change.
+pub fn execute_proposal(proposal: &mut Account<Proposal>, action: ProposalAction) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    proposal.execute(&action, current_time)?;
+
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum ProposalAction {
+    UpdateFeeSchedule {
+        fee_schedule: FeeSchedule,
+    },
+    UpdateFeeTiers {
+        #[max_len(MAX_FEE_TIERS)]
+        fee_tiers: Vec<FeeTier>,
+    },
+    UpdateCreationBond {
+        creation_bond: u64,
+    },
+    UpdateGovernanceDelay {
+        governance_delay: i64,
+    },
+    UpdateExposureLimits {
+        exposure_limits: ExposureLimits,
+    },
+    UpdateDisputePolicy {
+        dispute_policy: DisputePolicy,
+    },
+    UpdateMarketLimits {
+        market: Pubkey,
+        exposure_limits: ExposureLimits,
+    },
+    UpdatePriceFeed {
+        price_feed: Pubkey,
+        params: PriceFeedParams,
+    },
+    EnablePriceFeed {
+        price_feed: Pubkey,
+    },
+    GrantRole {
+        role: Role,
+        member: Pubkey,
+    },
+    RevokeRole {
+        role: Role,
+        member: Pubkey,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ProposalStatus {
+    Queued,
+    Executed,
+    Cancelled,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub market: Pubkey,
+    pub user_position: Pubkey,
+    pub referrer: Pubkey,
+    pub volume: u64,
+    pub claimed: bool,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    /// Manages config, the price feed registry and role grants.
+    Admin,
+    /// Creates markets without posting a creation bond.
+    MarketCreator,
+    Resolver,
+    /// Pauses and resumes the program or individual markets.
+    Guardian,
+    /// Withdraws the protocol's share of the fees.
+    Treasury,
+    /// Settles disputes raised against market resolutions.
+    Arbiter,
+}
+
+/// Holding a role means owning the `RoleAssignment` PDA derived from the role
+/// and the member's wallet; revoking a role closes it.
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    pub role: Role,
+    pub member: Pubkey,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+}
@@ -1,10 +1,32 @@
 use anchor_lang::prelude::*;
 
 #[account]
+#[derive(InitSpace)]
 pub struct UserPosition {
+    pub version: u8,
     pub market: Pubkey,
     pub user: Pubkey,
     pub yes_shares: u64,
     pub no_shares: u64,
     pub claimed: bool,
+    pub referred: bool,
+    pub bet_count: u64,
+    /// Lamports paid for the open yes and no shares. Refunds, voids and PnL
+    /// are computed from these rather than from the share counts.
+    pub deposited_yes: u64,
+    pub deposited_no: u64,
+    /// Lifetime lamports returned by cancellations.
+    pub refunded: u64,
+    pub claimed_amount: u64,
+    pub bump: u8,
+    /// Room for new fields without another realloc.
+    pub reserved: [u8; 31],
+}
+
+impl UserPosition {
+    pub const VERSION: u8 = 3;
+
+    pub fn cost_basis(&self) -> u64 {
+        self.deposited_yes + self.deposited_no
+    }
 }
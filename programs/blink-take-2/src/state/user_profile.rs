@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_REFERRAL_CODE_LEN;
+
+/// Cross-market statistics for a wallet, kept up to date by every bet,
+/// cancellation and claim.
+#[account]
+#[derive(InitSpace)]
+pub struct UserProfile {
+    pub user: Pubkey,
+    pub total_volume: u64,
+    /// Stakes of claimed positions on resolved markets. Unlike
+    /// `total_volume` it can no longer be cancelled, so fee tiers are
+    /// resolved against it.
+    pub settled_volume: u64,
+    pub markets_entered: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub realized_pnl: i64,
+    #[max_len(MAX_REFERRAL_CODE_LEN)]
+    pub referral_code: String,
+}
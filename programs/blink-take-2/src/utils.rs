@@ -1,41 +1,117 @@
+use crate::constants::{MAX_MARKET_DURATION, MAX_SYMBOL_LEN, MIN_MARKET_DURATION};
 use crate::errors::ErrorCode;
+use crate::state::PriceFeedConfig;
 use anchor_lang::prelude::*;
+use blink_core::pricing::confidence_within;
 use pyth_sdk_solana::state::SolanaPriceAccount;
 
-pub fn fetch_pyth_price(price_feed_info: &AccountInfo) -> Result<i64> {
+pub fn fetch_pyth_price(
+    price_feed_info: &AccountInfo,
+    price_feed_config: &PriceFeedConfig,
+) -> Result<i64> {
     let price_feed = SolanaPriceAccount::account_info_to_feed(price_feed_info)
         .map_err(|_| ErrorCode::PriceFetchFailed)?;
 
-    let price = price_feed.get_price_unchecked();
+    let current_time = Clock::get()?.unix_timestamp;
+    let price = price_feed
+        .get_price_no_older_than(current_time, price_feed_config.max_staleness)
+        .ok_or(ErrorCode::StalePrice)?;
+
+    if !confidence_within(
+        price.price,
+        price.conf,
+        price_feed_config.max_confidence_bps,
+    ) {
+        return Err(ErrorCode::PriceConfidenceTooWide.into());
+    }
+
     Ok(price.price)
 }
 
-pub fn validate_active_period(start_time: u64, duration: u64) -> Result<()> {
-    let current_time = Clock::get()?.unix_timestamp as u64;
-    let end_time = start_time + duration;
+pub fn validate_duration(duration: u64) -> Result<()> {
+    if !(MIN_MARKET_DURATION..=MAX_MARKET_DURATION).contains(&duration) {
+        return Err(ErrorCode::InvalidDuration.into());
+    }
+
+    Ok(())
+}
+
+/// Symbols are 1 to `MAX_SYMBOL_LEN` uppercase ASCII letters or digits.
+pub fn validate_symbol(symbol: &str) -> Result<()> {
+    if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LEN {
+        return Err(ErrorCode::InvalidSymbol.into());
+    }
 
-    if current_time < start_time || current_time > end_time {
-        return Err(ErrorCode::MarketNotActive.into());
+    if !symbol
+        .bytes()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    {
+        return Err(ErrorCode::InvalidSymbol.into());
     }
 
     Ok(())
 }
 
-pub fn calculate_shares(amount: u64) -> u64 {
-    amount
+/// Zero-pads a validated symbol into the fixed-size form stored on `Market`.
+pub fn symbol_to_bytes(symbol: &str) -> [u8; MAX_SYMBOL_LEN] {
+    let mut bytes = [0; MAX_SYMBOL_LEN];
+    bytes[..symbol.len()].copy_from_slice(symbol.as_bytes());
+    bytes
 }
 
-pub fn calculate_refund_amount(
-    original_amount: u64,
-    elapsed_time: u64,
-    total_duration: u64,
-) -> u64 {
-    if elapsed_time >= total_duration {
-        return 0;
+/// Checks that an account awaiting migration belongs to this program and has
+/// the expected discriminator, and returns its data length.
+pub fn check_legacy_account(account: &AccountInfo, discriminator: [u8; 8]) -> Result<usize> {
+    if account.owner != &crate::ID {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
     }
 
-    let time_ratio = (total_duration - elapsed_time) as f64 / total_duration as f64;
-    let refund_ratio = time_ratio.powf(1.5);
+    let data = account.try_borrow_data()?;
+    if data.len() < 8 || data[..8] != discriminator {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
 
-    (original_amount as f64 * refund_ratio) as u64
+    Ok(data.len())
+}
+
+/// The error for an account that is not in the first release's layout. Every
+/// versioned layout shipped at once, so such an account either is already at
+/// `version` and `len` bytes long, or was never written by a release.
+pub fn versioned_account_error(
+    data: &[u8],
+    version_offset: usize,
+    version: u8,
+    len: usize,
+) -> Error {
+    match data.get(version_offset) {
+        Some(&current) if current == version && data.len() == len => {
+            ErrorCode::AccountAlreadyMigrated.into()
+        }
+        _ => ErrorCode::UnsupportedAccountVersion.into(),
+    }
+}
+
+/// Grows an account to `new_len`, topping up its rent from `payer` first.
+pub fn realloc_with_rent<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(new_len);
+    let top_up = rent_exempt_minimum.saturating_sub(account.lamports());
+    if top_up > 0 {
+        let cpi_context = CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, top_up)?;
+    }
+
+    account.realloc(new_len, false)?;
+
+    Ok(())
 }
@@ -0,0 +1,188 @@
+//! Runs `create_market` in a local bank and reads the market back through its
+//! zero-copy layout. The config and the price feed registration are written
+//! straight into the bank, and the price account is a hand-built Pyth price.
+
+use std::mem::size_of;
+
+use anchor_lang::prelude::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{AccountSerialize, Discriminator, InstructionData, Owner, Space, ToAccountMetas};
+use blink_take_2::constants::MARKET_CREATION_AUTHORITY;
+use blink_take_2::state::{
+    Config, FeeSchedule, Market, MarketStatus, OracleSource, PriceFeed, PriceFeedConfig,
+};
+use blink_take_2::{pda, ID};
+use pyth_sdk_solana::state::{AccountType, PriceStatus, SolanaPriceAccount, MAGIC, VERSION_2};
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+const NOW: i64 = 1_700_000_000;
+const MARKET_ID: u64 = 7;
+const DURATION: u64 = 3600;
+const PRICE: i64 = 1_000_000;
+const CREATION_BOND: u64 = 100_000_000;
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // anchor's entry ties the slice to the lifetime of the accounts it holds
+    let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+    blink_take_2::entry(program_id, accounts, data)
+}
+
+fn program_account<T: AccountSerialize>(account: &T, space: usize) -> Account {
+    let mut data = Vec::with_capacity(space);
+    account.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn price_account(price: i64, publish_time: i64) -> Account {
+    let mut account: SolanaPriceAccount = bytemuck::Zeroable::zeroed();
+    account.magic = MAGIC;
+    account.ver = VERSION_2;
+    account.atype = AccountType::Price as u32;
+    account.size = size_of::<SolanaPriceAccount>() as u32;
+    account.expo = -6;
+    account.timestamp = publish_time;
+    account.agg.price = price;
+    account.agg.conf = 1;
+    account.agg.status = PriceStatus::Trading;
+
+    Account {
+        lamports: 1_000_000_000,
+        data: bytemuck::bytes_of(&account).to_vec(),
+        owner: PriceFeed::owner(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+async fn create_market_writes_the_zero_copy_layout() {
+    let mut test = ProgramTest::new("blink_take_2", ID, processor!(process_instruction));
+    let creator = Keypair::new();
+    let price_feed = Pubkey::new_unique();
+
+    test.add_account(
+        creator.pubkey(),
+        Account::new(100_000_000_000, 0, &solana_sdk::system_program::ID),
+    );
+
+    let config_address = pda::config_address().0;
+    let config = Config {
+        authority: MARKET_CREATION_AUTHORITY,
+        fee_schedule: FeeSchedule {
+            protocol_bps: 100,
+            creator_bps: 50,
+            referrer_bps: 25,
+            resolver_bps: 25,
+        },
+        creation_bond: CREATION_BOND,
+        fee_tiers: vec![],
+        governance_delay: 0,
+        exposure_limits: Default::default(),
+        dispute_policy: Default::default(),
+        pause: Default::default(),
+    };
+    test.add_account(
+        config_address,
+        program_account(&config, 8 + Config::INIT_SPACE),
+    );
+
+    let (price_feed_config_address, price_feed_config_bump) =
+        pda::price_feed_config_address(&price_feed);
+    let price_feed_config = PriceFeedConfig {
+        version: PriceFeedConfig::VERSION,
+        price_feed,
+        symbol: "BONK".to_string(),
+        decimals: 6,
+        oracle_source: OracleSource::Pyth,
+        max_staleness: 60,
+        max_confidence_bps: 100,
+        enabled: true,
+        open_markets: 0,
+        bump: price_feed_config_bump,
+        reserved: [0; 23],
+    };
+    test.add_account(
+        price_feed_config_address,
+        program_account(&price_feed_config, 8 + PriceFeedConfig::INIT_SPACE),
+    );
+    test.add_account(price_feed, price_account(PRICE, NOW));
+
+    let mut context = test.start_with_context().await;
+    let clock = Clock {
+        unix_timestamp: NOW,
+        ..context.banks_client.get_sysvar::<Clock>().await.unwrap()
+    };
+    context.set_sysvar(&clock);
+
+    let (market_address, market_bump) = pda::market_address(&creator.pubkey(), MARKET_ID);
+    let instruction = Instruction {
+        program_id: ID,
+        accounts: blink_take_2::accounts::CreateMarket {
+            market: market_address,
+            config: config_address,
+            price_feed_config: price_feed_config_address,
+            price_feed,
+            creator_role: None,
+            authority: creator.pubkey(),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: blink_take_2::instruction::CreateMarket {
+            market_id: MARKET_ID,
+            duration: DURATION,
+        }
+        .data(),
+    };
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&creator.pubkey()),
+        &[&creator],
+        blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(market_address)
+        .await
+        .unwrap()
+        .expect("market exists");
+    assert_eq!(account.data.len(), 8 + size_of::<Market>());
+    assert_eq!(account.data[..8], Market::DISCRIMINATOR);
+
+    let market: Market = bytemuck::pod_read_unaligned(&account.data[8..]);
+    assert_eq!(market.version, Market::VERSION);
+    assert_eq!(market.status(), MarketStatus::Open);
+    assert_eq!(market.authority, creator.pubkey());
+    assert_eq!(market.market_id, MARKET_ID);
+    assert_eq!(market.feed_id, price_feed);
+    assert_eq!(market.symbol(), "BONK");
+    assert_eq!(market.start_time, NOW as u64);
+    assert_eq!(market.duration, DURATION);
+    assert_eq!(market.initial_price, PRICE);
+    assert_eq!(market.creator_bond, CREATION_BOND);
+    assert_eq!(market.winning_outcome(), None);
+    assert_eq!(market.bump, market_bump);
+}
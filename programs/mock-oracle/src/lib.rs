@@ -0,0 +1,65 @@
+//! Price accounts in Pyth's layout whose price is set by instruction, so
+//! local tests can script price moves. Build blink with the `mock-oracle`
+//! feature to have its `PriceFeed` accept them.
+//!
+//! Anyone can set any price: never deploy this outside a local validator.
+
+use anchor_lang::prelude::*;
+use pyth_sdk_solana::state::{AccountType, PriceStatus, SolanaPriceAccount, MAGIC, VERSION_2};
+
+declare_id!("Fv2yNJjn7Pqsn1dLdvz3BfSTTzuviX9gaRKVzCXWRipg");
+
+/// Space of a price account, the same as Pyth's.
+pub const PRICE_ACCOUNT_SIZE: usize = std::mem::size_of::<SolanaPriceAccount>();
+
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    /// Publishes `price ± conf` scaled by `10^expo` at `publish_time`. The
+    /// price account must be created beforehand with `PRICE_ACCOUNT_SIZE`
+    /// zeroed bytes and this program as owner.
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.price_account.try_borrow_mut_data()?;
+        if data.len() != PRICE_ACCOUNT_SIZE {
+            return Err(ErrorCode::InvalidPriceAccountSize.into());
+        }
+
+        let mut account: SolanaPriceAccount = bytemuck::pod_read_unaligned(&data[..]);
+        account.magic = MAGIC;
+        account.ver = VERSION_2;
+        account.atype = AccountType::Price as u32;
+        account.size = PRICE_ACCOUNT_SIZE as u32;
+        account.expo = expo;
+        account.timestamp = publish_time;
+        account.last_slot = Clock::get()?.slot;
+        account.agg.price = price;
+        account.agg.conf = conf;
+        account.agg.status = PriceStatus::Trading;
+        account.agg.pub_slot = account.last_slot;
+        account.ema_price.val = price;
+        account.ema_conf.val = conf as i64;
+        data.copy_from_slice(bytemuck::bytes_of(&account));
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    /// CHECK: written in Pyth's price account layout, see `set_price`
+    #[account(mut, owner = crate::ID)]
+    pub price_account: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Price account has the wrong size")]
+    InvalidPriceAccountSize,
+}
@@ -0,0 +1,44 @@
+// Prints the compute units of the first successful placeBet and claimWinnings
+// sent to the program on the local validator, which are the calls the
+// benchmark test measures.
+import { Connection, PublicKey } from "@solana/web3.js";
+
+const instructions = {
+  placeBet: "Instruction: PlaceBet",
+  claimWinnings: "Instruction: ClaimWinnings",
+};
+
+const connection = new Connection("http://127.0.0.1:8899", "confirmed");
+const programId = new PublicKey(process.argv[2]);
+
+const signatures = [];
+let before;
+for (;;) {
+  const page = await connection.getSignaturesForAddress(programId, { before });
+  if (page.length === 0) break;
+  signatures.push(...page);
+  before = page[page.length - 1].signature;
+}
+
+const computeUnits = {};
+// oldest first, so the first match is the call the suite measures
+for (const { signature, err } of signatures.reverse()) {
+  if (err) continue;
+  const transaction = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const logs = transaction?.meta?.logMessages ?? [];
+  for (const [name, log] of Object.entries(instructions)) {
+    if (computeUnits[name] === undefined && logs.some((l) => l.endsWith(log))) {
+      computeUnits[name] = transaction.meta.computeUnitsConsumed;
+    }
+  }
+}
+
+for (const name of Object.keys(instructions)) {
+  if (computeUnits[name] === undefined) {
+    throw new Error(`no successful ${name} on ${programId}`);
+  }
+}
+console.log(JSON.stringify(computeUnits, null, 2));
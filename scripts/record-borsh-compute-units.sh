@@ -0,0 +1,35 @@
+#!/usr/bin/env bash
+# Records tests/fixtures/compute_units_borsh.json, the compute units placeBet
+# and claimWinnings cost while Market was still Borsh-deserialized.
+#
+# The program and test suite are checked out at the commit before Market went
+# zero-copy (or the commit given as the first argument), built and run against
+# a local validator, and the units are read back from the validator's
+# transaction history, so nothing in the old suite needs to record them.
+set -euo pipefail
+
+root=$(git rev-parse --show-toplevel)
+commit=${1:-$(git -C "$root" log -1 --format=%H --grep="Load Market zero-copy")^}
+program_id=$(grep -m1 '^blink_take_2' "$root/Anchor.toml" | cut -d'"' -f2)
+worktree=$(mktemp -d)
+
+cleanup() {
+  [[ -n "${validator:-}" ]] && kill "$validator" 2>/dev/null || true
+  git -C "$root" worktree remove --force "$worktree"
+}
+git -C "$root" worktree add --detach "$worktree" "$commit"
+trap cleanup EXIT
+ln -s "$root/node_modules" "$worktree/node_modules"
+
+cd "$worktree"
+anchor build
+anchor localnet --skip-build >/dev/null 2>&1 &
+validator=$!
+until solana cluster-version -u localhost >/dev/null 2>&1; do sleep 1; done
+
+# the old suite only has to get as far as the first claim
+anchor test --skip-local-validator --skip-build --skip-deploy || true
+
+node "$root/scripts/compute-units.mjs" "$program_id" \
+  > "$root/tests/fixtures/compute_units_borsh.json"
+cat "$root/tests/fixtures/compute_units_borsh.json"
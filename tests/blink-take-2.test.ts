@@ -6,13 +6,39 @@ import {
   PublicKey,
   SystemProgram,
 } from "@solana/web3.js";
-import crypto from "node:crypto";
+import fs from "node:fs";
+import path from "node:path";
 import { suite, test, expect, assert } from "vitest";
 
+import { MARKET_OFFSETS, marketFilters } from "./filters";
 import { PDAHelper } from "./pda";
-import { TEAM_WALLET } from "./constants";
+import {
+  CREATION_BOND,
+  DISPUTE_POLICY,
+  EXPOSURE_LIMITS,
+  FEE_SCHEDULE,
+  GOVERNANCE_DELAY,
+  LEGACY_FIXTURES,
+  MARKET_OUTCOME,
+  MARKET_STATUS,
+  MARKET_VOID_REASON,
+  PRICE_FEED_PARAMS,
+  ROLE,
+  TEAM_WALLET,
+} from "./constants";
 import { BlinkTake2 } from "../target/types/blink_take_2";
-import { calculateShares } from "./utils";
+import {
+  calculateFeeRebate,
+  calculateShares,
+  calculateTotalFees,
+  decodeSymbol,
+  fetchComputeUnits,
+  fetchEvents,
+  randomMarketId,
+  randomProposalId,
+  waitForExpiry,
+  zeroCopyFieldOffsets,
+} from "./utils";
 
 suite("blink-take-2", () => {
   const provider = anchor.AnchorProvider.env();
@@ -24,11 +50,66 @@ suite("blink-take-2", () => {
   const connection = provider.connection;
   const authority = provider.publicKey;
   const user = Keypair.generate();
+  const referredUser = Keypair.generate();
+  const referrer = Keypair.generate();
   const feed = new PublicKey("J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix");
 
+  let configPDA: PublicKey;
   let priceFeedConfigPDA: PublicKey;
   let marketPDA: PublicKey;
   let userPositionPDA: PublicKey;
+  let referralPDA: PublicKey;
+  let referredUserPositionPDA: PublicKey;
+  let betReceiptPDA: PublicKey;
+
+  // compute units of the zero-copy hot paths, reported by the benchmark test
+  const computeUnits: Record<string, number> = {};
+  // the same calls while Market was still Borsh-deserialized, see the
+  // benchmark test
+  const borshComputeUnitsPath = path.join(
+    __dirname,
+    "fixtures",
+    "compute_units_borsh.json"
+  );
+
+  // admin changes go through the timelock; the suite runs with no delay
+  const queueProposal = async (action: any) => {
+    const proposalId = randomProposalId();
+    const proposalPDA = pdaHelper.proposal(proposalId);
+
+    await program.methods
+      .queueProposal(proposalId, action)
+      .accounts({
+        authority,
+        adminRole: pdaHelper.role(ROLE.admin),
+        config: configPDA,
+        proposal: proposalPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return proposalPDA;
+  };
+
+  // enabling a feed goes through the timelock, disabling it does not
+  const enablePriceFeed = async (
+    priceFeed: PublicKey,
+    priceFeedConfig: PublicKey
+  ) => {
+    const proposalPDA = await queueProposal({
+      enablePriceFeed: { priceFeed },
+    });
+
+    await program.methods
+      .enablePriceFeed()
+      .accounts({
+        adminRole: pdaHelper.role(ROLE.admin),
+        authority,
+        priceFeedConfig,
+        proposal: proposalPDA,
+      })
+      .rpc();
+  };
 
   test("airdrop SOL to user", async () => {
     const signature = await connection.requestAirdrop(
@@ -41,46 +122,56 @@ suite("blink-take-2", () => {
     expect(balance).toBe(2 * LAMPORTS_PER_SOL);
   });
 
-  test("initialize price feed with authorized wallet", async () => {
-    priceFeedConfigPDA = pdaHelper.priceFeedConfig(feed);
+  test("initialize config with authorized wallet", async () => {
+    configPDA = pdaHelper.config();
 
     try {
       await program.methods
-        .initializePriceFeed(feed)
+        .initializeConfig(
+          FEE_SCHEDULE,
+          new anchor.BN(CREATION_BOND),
+          new anchor.BN(GOVERNANCE_DELAY),
+          EXPOSURE_LIMITS,
+          DISPUTE_POLICY
+        )
         .accounts({
-          payer: authority,
-          priceFeedConfig: priceFeedConfigPDA,
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      const priceFeedConfigAccountData =
-        await program.account.priceFeedConfig.fetch(priceFeedConfigPDA);
-      expect(priceFeedConfigAccountData.priceFeed.toString()).toBe(
-        feed.toString()
-      );
+      const configAccountData = await program.account.config.fetch(configPDA);
+      expect(configAccountData.authority.toString()).toBe(authority.toString());
+      expect(configAccountData.feeSchedule).toEqual(FEE_SCHEDULE);
+      expect(configAccountData.creationBond.toNumber()).toBe(CREATION_BOND);
     } catch (err) {
       console.log(err);
       assert.fail("unexpected error");
     }
   });
 
-  test("initialize price feed with unauthorized wallet", async () => {
-    priceFeedConfigPDA = pdaHelper.priceFeedConfig(feed);
+  test("update fee schedule above the maximum total fee", async () => {
+    const feeSchedule = { ...FEE_SCHEDULE, protocolBps: 5_000 };
 
     try {
+      const proposalPDA = await queueProposal({
+        updateFeeSchedule: { feeSchedule },
+      });
+
       await program.methods
-        .initializePriceFeed(feed)
+        .updateFeeSchedule(feeSchedule)
         .accounts({
-          payer: user.publicKey,
-          priceFeedConfig: priceFeedConfigPDA,
-          systemProgram: SystemProgram.programId,
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: proposalPDA,
         })
-        .signers([user])
         .rpc();
     } catch (err) {
       if (err instanceof AnchorError) {
-        if (err.error.errorCode.number === 2006) {
+        if (err.error.errorMessage === "Invalid fee schedule") {
           assert.ok("test failed as expected");
           return;
         }
@@ -93,62 +184,63 @@ suite("blink-take-2", () => {
       assert.fail("unexpected error");
     }
 
-    assert.fail("expected test to fail as unauthorized creator was used");
+    assert.fail("expected test to fail as the fee schedule exceeds the maximum");
   });
 
-  test("create market with authorized creator", async () => {
-    const memeCoinSymbol = crypto.randomBytes(2).toString("hex");
-    const duration = new anchor.BN(10);
+  test("grant operational roles to the admin", async () => {
+    try {
+      const roles = [
+        { index: ROLE.resolver, role: { resolver: {} } },
+        { index: ROLE.guardian, role: { guardian: {} } },
+        { index: ROLE.treasury, role: { treasury: {} } },
+        { index: ROLE.arbiter, role: { arbiter: {} } },
+      ];
 
-    marketPDA = pdaHelper.market(memeCoinSymbol);
+      for (const { index, role } of roles) {
+        const proposalPDA = await queueProposal({
+          grantRole: { role, member: authority },
+        });
 
-    try {
-      await program.methods
-        .createMarket(memeCoinSymbol, priceFeedConfigPDA.toBase58(), duration)
-        .accounts({
-          authority,
-          market: marketPDA,
-          priceFeedConfig: priceFeedConfigPDA,
-          priceFeed: feed,
-          teamWallet: TEAM_WALLET,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
+        await program.methods
+          .grantRole(role, authority)
+          .accounts({
+            authority,
+            adminRole: pdaHelper.role(ROLE.admin),
+            proposal: proposalPDA,
+            roleAssignment: pdaHelper.role(index),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
 
-      const marketAccountData = await program.account.market.fetch(marketPDA);
-      expect(marketAccountData.memecoinSymbol).toBe(memeCoinSymbol);
-      expect(marketAccountData.feedId.toString()).toBe(
-        priceFeedConfigPDA.toString()
-      );
-      expect(marketAccountData.authority.toString()).toBe(authority.toString());
-      expect(marketAccountData.duration.toNumber()).toBe(duration.toNumber());
-      expect(marketAccountData.resolved).toBe(false);
+        const roleAccountData = await program.account.roleAssignment.fetch(
+          pdaHelper.role(index)
+        );
+        expect(roleAccountData.role).toEqual(role);
+        expect(roleAccountData.member.toString()).toBe(authority.toString());
+        expect(roleAccountData.grantedBy.toString()).toBe(authority.toString());
+      }
     } catch (err) {
       console.log(err);
       assert.fail("unexpected error");
     }
   });
 
-  test("create market with unauthorized creator", async () => {
-    const memeCoinSymbol = crypto.randomBytes(2).toString("hex");
-    const duration = new anchor.BN(10);
-
+  test("grant role with a wallet without the admin role", async () => {
     try {
       await program.methods
-        .createMarket(memeCoinSymbol, priceFeedConfigPDA.toBase58(), duration)
+        .grantRole({ admin: {} }, user.publicKey)
         .accounts({
           authority: user.publicKey,
-          market: pdaHelper.market(memeCoinSymbol),
-          priceFeedConfig: priceFeedConfigPDA,
-          priceFeed: feed,
-          teamWallet: TEAM_WALLET,
+          adminRole: pdaHelper.role(ROLE.admin, user.publicKey),
+          proposal: pdaHelper.proposal(randomProposalId()),
+          roleAssignment: pdaHelper.role(ROLE.admin, user.publicKey),
           systemProgram: SystemProgram.programId,
         })
         .signers([user])
         .rpc();
     } catch (err) {
       if (err instanceof AnchorError) {
-        if (err.error.errorCode.number === 2006) {
+        if (err.error.errorCode.number === 3012) {
           assert.ok("test failed as expected");
           return;
         }
@@ -161,61 +253,64 @@ suite("blink-take-2", () => {
       assert.fail("unexpected error");
     }
 
-    assert.fail("expected test to fail as unauthorized creator is used");
+    assert.fail("expected test to fail as the signer is not an admin");
   });
 
-  test("create user for an existing market", async () => {
-    userPositionPDA = pdaHelper.userPosition(marketPDA, user.publicKey);
-
+  test("revoke own admin role", async () => {
     try {
+      const proposalPDA = await queueProposal({
+        revokeRole: { role: { admin: {} }, member: authority },
+      });
+
       await program.methods
-        .createUser()
+        .revokeRole()
         .accounts({
-          market: marketPDA,
-          user: user.publicKey,
-          userPosition: userPositionPDA,
-          systemProgram: SystemProgram.programId,
+          authority,
+          adminRole: pdaHelper.role(ROLE.admin),
+          proposal: proposalPDA,
+          roleAssignment: pdaHelper.role(ROLE.admin),
         })
-        .signers([user])
         .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (
+          err.error.errorMessage === "Admins cannot revoke their own admin role"
+        ) {
+          assert.ok("test failed as expected");
+          return;
+        }
 
-      const userPositionAccountData = await program.account.userPosition.fetch(
-        userPositionPDA
-      );
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
 
-      expect(userPositionAccountData.claimed).toBe(false);
-      expect(userPositionAccountData.market.toString()).toBe(
-        marketPDA.toString()
-      );
-      expect(userPositionAccountData.noShares.toNumber()).toBe(0);
-      expect(userPositionAccountData.yesShares.toNumber()).toBe(0);
-      expect(userPositionAccountData.user.toString()).toBe(
-        user.publicKey.toString()
-      );
-    } catch (err) {
       console.log(err);
       assert.fail("unexpected error");
     }
-  });
 
-  test("create an user for non-existing market", async () => {
-    const marketPDA = pdaHelper.market(crypto.randomBytes(2).toString("hex"));
-    const userPositionPDA = pdaHelper.userPosition(marketPDA, user.publicKey);
+    assert.fail("expected test to fail as admins cannot revoke themselves");
+  });
 
+  test("execute a proposal that does not match the requested change", async () => {
     try {
+      const proposalPDA = await queueProposal({
+        updateCreationBond: { creationBond: new anchor.BN(CREATION_BOND) },
+      });
+
       await program.methods
-        .createUser()
+        .updateCreationBond(new anchor.BN(0))
         .accounts({
-          market: marketPDA,
-          user: user.publicKey,
-          userPosition: userPositionPDA,
-          systemProgram: SystemProgram.programId,
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: proposalPDA,
         })
-        .signers([user])
         .rpc();
     } catch (err) {
       if (err instanceof AnchorError) {
-        if (err.error.errorCode.number === 3012) {
+        if (
+          err.error.errorMessage === "Proposal does not match the requested change"
+        ) {
           assert.ok("test failed as expected");
           return;
         }
@@ -228,78 +323,148 @@ suite("blink-take-2", () => {
       assert.fail("unexpected error");
     }
 
-    assert.fail("expected test to fail as the market wasn't yet created");
+    assert.fail("expected test to fail as the proposal is for a different bond");
   });
 
-  test("place a bet", async () => {
-    const amount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
-    const yesChoice = true;
-    const noChoice = false;
+  test("execute a proposal before the governance delay elapses", async () => {
+    const delay = new anchor.BN(24 * 60 * 60);
+    const creationBond = new anchor.BN(2 * CREATION_BOND);
+
+    // queued while the delay is still zero, so it can restore it afterwards
+    const resetProposalPDA = await queueProposal({
+      updateGovernanceDelay: { governanceDelay: new anchor.BN(GOVERNANCE_DELAY) },
+    });
+    const delayProposalPDA = await queueProposal({
+      updateGovernanceDelay: { governanceDelay: delay },
+    });
+
+    await program.methods
+      .updateGovernanceDelay(delay)
+      .accounts({
+        adminRole: pdaHelper.role(ROLE.admin),
+        authority,
+        config: configPDA,
+        proposal: delayProposalPDA,
+      })
+      .rpc();
+
+    const proposalPDA = await queueProposal({
+      updateCreationBond: { creationBond },
+    });
+
+    const proposalAccountData = await program.account.proposal.fetch(
+      proposalPDA
+    );
+    expect(
+      proposalAccountData.executableAt
+        .sub(proposalAccountData.queuedAt)
+        .eq(delay)
+    ).toBe(true);
 
     try {
       await program.methods
-        .placeBet(amount, yesChoice)
+        .updateCreationBond(creationBond)
         .accounts({
-          market: marketPDA,
-          user: user.publicKey,
-          userPosition: userPositionPDA,
-          systemProgram: SystemProgram.programId,
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: proposalPDA,
         })
-        .signers([user])
         .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Proposal timelock has not expired") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
 
+      console.log(err);
+      assert.fail("unexpected error");
+    } finally {
       await program.methods
-        .placeBet(amount, noChoice)
+        .cancelProposal()
         .accounts({
-          market: marketPDA,
-          user: user.publicKey,
-          userPosition: userPositionPDA,
-          systemProgram: SystemProgram.programId,
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          proposal: proposalPDA,
         })
-        .signers([user])
         .rpc();
 
-      const userPositionAccountData = await program.account.userPosition.fetch(
-        userPositionPDA
+      const cancelledProposalData = await program.account.proposal.fetch(
+        proposalPDA
       );
+      expect(cancelledProposalData.status).toEqual({ cancelled: {} });
 
-      expect(userPositionAccountData.claimed).toBe(false);
-      expect(userPositionAccountData.market.toString()).toBe(
-        marketPDA.toString()
-      );
-      expect(userPositionAccountData.noShares.toNumber()).toBe(
-        calculateShares(amount.toNumber())
-      );
-      expect(userPositionAccountData.yesShares.toNumber()).toBe(
-        calculateShares(amount.toNumber())
+      await program.methods
+        .updateGovernanceDelay(new anchor.BN(GOVERNANCE_DELAY))
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: resetProposalPDA,
+        })
+        .rpc();
+    }
+
+    assert.fail("expected test to fail as the proposal is still timelocked");
+  });
+
+  test("initialize price feed with authorized wallet", async () => {
+    priceFeedConfigPDA = pdaHelper.priceFeedConfig(feed);
+
+    try {
+      const signature = await program.methods
+        .initializePriceFeed(feed, PRICE_FEED_PARAMS)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          payer: authority,
+          priceFeedConfig: priceFeedConfigPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      const priceFeedConfigAccountData =
+        await program.account.priceFeedConfig.fetch(priceFeedConfigPDA);
+      expect(priceFeedConfigAccountData.priceFeed.toString()).toBe(
+        feed.toString()
       );
-      expect(userPositionAccountData.user.toString()).toBe(
-        user.publicKey.toString()
+      expect(priceFeedConfigAccountData.symbol).toBe(PRICE_FEED_PARAMS.symbol);
+      expect(priceFeedConfigAccountData.enabled).toBe(true);
+      expect(priceFeedConfigAccountData.bump).toBe(
+        pdaHelper.priceFeedConfigBump(feed)
       );
+
+      const [event] = await fetchEvents(program, signature);
+      expect(event.name).toBe("PriceFeedRegistered");
+      expect(event.data.priceFeed.toString()).toBe(feed.toString());
+      expect(event.data.symbol).toBe(PRICE_FEED_PARAMS.symbol);
     } catch (err) {
       console.log(err);
       assert.fail("unexpected error");
     }
   });
 
-  test("place a bet lower than 90k lamports", async () => {
-    const amount = new anchor.BN(90_000);
-    const yesChoice = true;
+  test("initialize price feed with a wallet without the admin role", async () => {
+    priceFeedConfigPDA = pdaHelper.priceFeedConfig(feed);
 
     try {
       await program.methods
-        .placeBet(amount, yesChoice)
+        .initializePriceFeed(feed, PRICE_FEED_PARAMS)
         .accounts({
-          market: marketPDA,
-          user: user.publicKey,
-          userPosition: userPositionPDA,
+          adminRole: pdaHelper.role(ROLE.admin, user.publicKey),
+          payer: user.publicKey,
+          priceFeedConfig: priceFeedConfigPDA,
           systemProgram: SystemProgram.programId,
         })
         .signers([user])
         .rpc();
     } catch (err) {
       if (err instanceof AnchorError) {
-        if (err.error.errorMessage === "Bet amount is too low") {
+        if (err.error.errorCode.number === 3012) {
           assert.ok("test failed as expected");
           return;
         }
@@ -312,121 +477,2076 @@ suite("blink-take-2", () => {
       assert.fail("unexpected error");
     }
 
-    assert.fail("test should have failed as the bet amount is too low");
+    assert.fail("expected test to fail as the payer does not hold the admin role");
   });
 
-  test("place a bet with insufficient lamports", async () => {
-    const amount = new anchor.BN(10 * LAMPORTS_PER_SOL);
-    const yesChoice = true;
+  test("create market with authorized creator", async () => {
+    const marketId = randomMarketId();
+    const duration = new anchor.BN(60);
+
+    marketPDA = pdaHelper.market(marketId);
 
     try {
-      await program.methods
-        .placeBet(amount, yesChoice)
+      const signature = await program.methods
+        .createMarket(marketId, duration)
         .accounts({
+          authority,
           market: marketPDA,
-          user: user.publicKey,
-          userPosition: userPositionPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
           systemProgram: SystemProgram.programId,
         })
-        .signers([user])
-        .rpc();
-    } catch (err) {
-      if (err instanceof AnchorError) {
-        if (err.error.errorMessage === "Insufficient user funds") {
-          assert.ok("test failed as expected");
-          return;
-        }
+        .rpc({ commitment: "confirmed" });
 
-        console.log(err);
-        assert.fail("unexpected anchor error");
-      }
+      const marketAccountData = await program.account.market.fetch(marketPDA);
+      expect(marketAccountData.marketId.eq(marketId)).toBe(true);
+      expect(decodeSymbol(marketAccountData.memecoinSymbol)).toBe(
+        PRICE_FEED_PARAMS.symbol
+      );
+      expect(marketAccountData.feedId.toBase58()).toBe(feed.toBase58());
+      expect(marketAccountData.authority.toString()).toBe(authority.toString());
+      expect(marketAccountData.duration.toNumber()).toBe(duration.toNumber());
+      expect(marketAccountData.status).toBe(MARKET_STATUS.open);
+      expect(marketAccountData.feeSchedule).toEqual(FEE_SCHEDULE);
+      expect(marketAccountData.creatorBond.toNumber()).toBe(CREATION_BOND);
+      expect(marketAccountData.bump).toBe(pdaHelper.marketBump(marketId));
 
+      const [event] = await fetchEvents(program, signature);
+      expect(event.name).toBe("MarketCreated");
+      expect(event.data.market.toString()).toBe(marketPDA.toString());
+      expect(event.data.marketId.eq(marketId)).toBe(true);
+      expect(event.data.duration.toNumber()).toBe(duration.toNumber());
+      expect(event.data.initialPrice.eq(marketAccountData.initialPrice)).toBe(
+        true
+      );
+    } catch (err) {
       console.log(err);
       assert.fail("unexpected error");
     }
+  });
 
-    assert.fail(
-      "test should have failed as the user has insufficient funds to bet"
+  test("filter markets by status and authority", async () => {
+    const openMarkets = await program.account.market.all(
+      marketFilters(program, { status: "open", authority, feedId: feed })
+    );
+    expect(
+      openMarkets.some((market) => market.publicKey.equals(marketPDA))
+    ).toBe(true);
+    expect(
+      openMarkets.every((market) => market.account.authority.equals(authority))
+    ).toBe(true);
+
+    const resolvedMarkets = await program.account.market.all(
+      marketFilters(program, { status: "resolved", authority })
     );
+    expect(
+      resolvedMarkets.some((market) => market.publicKey.equals(marketPDA))
+    ).toBe(false);
   });
 
-  test("cancel bet", async () => {
+  test("market filter offsets match the IDL layout", () => {
+    const offsets = zeroCopyFieldOffsets(program.idl, "Market");
+    // the fields are summed in order, so the last one must end the account
+    expect(offsets.reserved + 56).toBe(program.account.market.size);
+    expect(MARKET_OFFSETS).toEqual({
+      version: offsets.version,
+      status: offsets.status,
+      authority: offsets.authority,
+      feedId: offsets.feedId,
+      symbol: offsets.memecoinSymbol,
+      marketId: offsets.marketId,
+      startTime: offsets.startTime,
+      duration: offsets.duration,
+    });
+  });
+
+  test("create market with permissionless creator", async () => {
+    const marketId = randomMarketId();
+    const duration = new anchor.BN(60);
+    const userMarketPDA = pdaHelper.market(marketId, user.publicKey);
+
     try {
       await program.methods
-        .cancelBet()
+        .createMarket(marketId, duration)
         .accounts({
-          market: marketPDA,
-          user: user.publicKey,
-          userPosition: userPositionPDA,
+          authority: user.publicKey,
+          market: userMarketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
           systemProgram: SystemProgram.programId,
         })
         .signers([user])
         .rpc();
 
-      const userPositionAccountData = await program.account.userPosition.fetch(
-        userPositionPDA
-      );
-
-      expect(userPositionAccountData.claimed).toBe(false);
-      expect(userPositionAccountData.market.toString()).toBe(
-        marketPDA.toString()
+      const marketAccountData = await program.account.market.fetch(
+        userMarketPDA
       );
-      expect(userPositionAccountData.noShares.toNumber()).toBe(0);
-      expect(userPositionAccountData.yesShares.toNumber()).toBe(0);
-      expect(userPositionAccountData.user.toString()).toBe(
+      expect(marketAccountData.authority.toString()).toBe(
         user.publicKey.toString()
       );
+      expect(marketAccountData.creatorBond.toNumber()).toBe(CREATION_BOND);
     } catch (err) {
       console.log(err);
       assert.fail("unexpected error");
     }
   });
 
-  test("resolve market", async () => {
+  test("create market as an approved market creator skips the bond", async () => {
+    const marketId = randomMarketId();
+    const creatorRolePDA = pdaHelper.role(ROLE.marketCreator);
+
     try {
+      const grantProposalPDA = await queueProposal({
+        grantRole: { role: { marketCreator: {} }, member: authority },
+      });
+
       await program.methods
-        .resolveMarket()
+        .grantRole({ marketCreator: {} }, authority)
         .accounts({
           authority,
-          market: marketPDA,
+          adminRole: pdaHelper.role(ROLE.admin),
+          proposal: grantProposalPDA,
+          roleAssignment: creatorRolePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .createMarket(marketId, new anchor.BN(60))
+        .accounts({
+          authority,
+          market: pdaHelper.market(marketId),
+          config: configPDA,
           priceFeedConfig: priceFeedConfigPDA,
           priceFeed: feed,
+          creatorRole: creatorRolePDA,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      const marketAccountData = await program.account.market.fetch(marketPDA);
-      expect(marketAccountData.resolved).toBe(true);
-      expect(marketAccountData.winningOutcome).not.toBeNull;
+      const marketAccountData = await program.account.market.fetch(
+        pdaHelper.market(marketId)
+      );
+      expect(marketAccountData.creatorBond.toNumber()).toBe(0);
+
+      const revokeProposalPDA = await queueProposal({
+        revokeRole: { role: { marketCreator: {} }, member: authority },
+      });
+
+      const signature = await program.methods
+        .revokeRole()
+        .accounts({
+          authority,
+          adminRole: pdaHelper.role(ROLE.admin),
+          proposal: revokeProposalPDA,
+          roleAssignment: creatorRolePDA,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      const [event] = await fetchEvents(program, signature);
+      expect(event.name).toBe("RoleRevoked");
+      expect(event.data.role).toEqual({ marketCreator: {} });
+      expect(await connection.getAccountInfo(creatorRolePDA)).toBeNull();
     } catch (err) {
       console.log(err);
       assert.fail("unexpected error");
     }
   });
 
-  test("claim winnings", async () => {
+  test("void market for creator misconfiguration slashes the bond", async () => {
+    const marketId = randomMarketId();
+    const userMarketPDA = pdaHelper.market(marketId, user.publicKey);
+
     try {
       await program.methods
+        .createMarket(marketId, new anchor.BN(60))
+        .accounts({
+          authority: user.publicKey,
+          market: userMarketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const teamWalletBalanceBefore = await connection.getBalance(TEAM_WALLET);
+
+      await program.methods
+        .voidMarket({ creatorMisconfiguration: {} })
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          market: userMarketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          teamWallet: TEAM_WALLET,
+        })
+        .rpc();
+
+      const marketAccountData = await program.account.market.fetch(
+        userMarketPDA
+      );
+      expect(marketAccountData.status).toBe(MARKET_STATUS.voided);
+      expect(marketAccountData.voidReason).toBe(
+        MARKET_VOID_REASON.creatorMisconfiguration
+      );
+      expect(marketAccountData.creatorBond.toNumber()).toBe(0);
+
+      const teamWalletBalanceAfter = await connection.getBalance(TEAM_WALLET);
+      expect(teamWalletBalanceAfter - teamWalletBalanceBefore).toBe(
+        CREATION_BOND
+      );
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("update price feed with an invalid symbol", async () => {
+    const params = { ...PRICE_FEED_PARAMS, symbol: "" };
+
+    try {
+      const proposalPDA = await queueProposal({
+        updatePriceFeed: { priceFeed: feed, params },
+      });
+
+      await program.methods
+        .updatePriceFeed(params)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          priceFeedConfig: priceFeedConfigPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid symbol") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the symbol is empty");
+  });
+
+  test("update price feed with a lowercase symbol", async () => {
+    const params = { ...PRICE_FEED_PARAMS, symbol: "sol" };
+
+    try {
+      const proposalPDA = await queueProposal({
+        updatePriceFeed: { priceFeed: feed, params },
+      });
+
+      await program.methods
+        .updatePriceFeed(params)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          priceFeedConfig: priceFeedConfigPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid symbol") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the symbol has lowercase characters");
+  });
+
+  test("update price feed with a symbol containing punctuation", async () => {
+    const params = { ...PRICE_FEED_PARAMS, symbol: "SOL-USD" };
+
+    try {
+      const proposalPDA = await queueProposal({
+        updatePriceFeed: { priceFeed: feed, params },
+      });
+
+      await program.methods
+        .updatePriceFeed(params)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          priceFeedConfig: priceFeedConfigPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid symbol") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the symbol has invalid characters");
+  });
+
+  test("update price feed with a symbol that is too long", async () => {
+    const params = { ...PRICE_FEED_PARAMS, symbol: "ABCDEFGHIJKLMNOPQ" };
+
+    try {
+      const proposalPDA = await queueProposal({
+        updatePriceFeed: { priceFeed: feed, params },
+      });
+
+      await program.methods
+        .updatePriceFeed(params)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          priceFeedConfig: priceFeedConfigPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid symbol") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the symbol is too long");
+  });
+
+  test("create market with zero duration", async () => {
+    try {
+      const marketId = randomMarketId();
+
+      await program.methods
+        .createMarket(marketId, new anchor.BN(0))
+        .accounts({
+          authority,
+          market: pdaHelper.market(marketId),
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid market duration") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the duration is zero");
+  });
+
+  test("create market with duration below the minimum", async () => {
+    try {
+      const marketId = randomMarketId();
+
+      await program.methods
+        .createMarket(marketId, new anchor.BN(59))
+        .accounts({
+          authority,
+          market: pdaHelper.market(marketId),
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid market duration") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the duration is too short");
+  });
+
+  test("create market with duration above the maximum", async () => {
+    try {
+      const marketId = randomMarketId();
+
+      await program.methods
+        .createMarket(marketId, new anchor.BN(30 * 24 * 60 * 60 + 1))
+        .accounts({
+          authority,
+          market: pdaHelper.market(marketId),
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid market duration") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the duration is too long");
+  });
+
+  test("create market with u64::MAX duration", async () => {
+    try {
+      const marketId = randomMarketId();
+
+      await program.methods
+        .createMarket(marketId, new anchor.BN("18446744073709551615"))
+        .accounts({
+          authority,
+          market: pdaHelper.market(marketId),
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid market duration") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the duration is too long");
+  });
+
+  test("close an enabled price feed", async () => {
+    try {
+      await program.methods
+        .closePriceFeed()
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          priceFeedConfig: priceFeedConfigPDA,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (
+          err.error.errorMessage ===
+          "Price feed must be disabled before it is closed"
+        ) {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the price feed is still enabled");
+  });
+
+  test("close a disabled price feed with open markets", async () => {
+    await program.methods
+      .disablePriceFeed()
+      .accounts({
+        adminRole: pdaHelper.role(ROLE.admin),
+        authority,
+        priceFeedConfig: priceFeedConfigPDA,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .closePriceFeed()
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          priceFeedConfig: priceFeedConfigPDA,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Price feed still has open markets") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    } finally {
+      await enablePriceFeed(feed, priceFeedConfigPDA);
+    }
+
+    assert.fail("expected test to fail as markets are still open on the feed");
+  });
+
+  test("create market on a disabled price feed", async () => {
+    const marketId = randomMarketId();
+
+    await program.methods
+      .disablePriceFeed()
+      .accounts({
+        adminRole: pdaHelper.role(ROLE.admin),
+        authority,
+        priceFeedConfig: priceFeedConfigPDA,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .createMarket(marketId, new anchor.BN(60))
+        .accounts({
+          authority,
+          market: pdaHelper.market(marketId),
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Price feed is disabled") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    } finally {
+      await enablePriceFeed(feed, priceFeedConfigPDA);
+    }
+
+    assert.fail("expected test to fail as the price feed is disabled");
+  });
+
+  test("create user for an existing market", async () => {
+    userPositionPDA = pdaHelper.userPosition(marketPDA, user.publicKey);
+
+    try {
+      await program.methods
+        .createUser()
+        .accounts({
+          market: marketPDA,
+          user: user.publicKey,
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const userPositionAccountData = await program.account.userPosition.fetch(
+        userPositionPDA
+      );
+
+      expect(userPositionAccountData.claimed).toBe(false);
+      expect(userPositionAccountData.market.toString()).toBe(
+        marketPDA.toString()
+      );
+      expect(userPositionAccountData.noShares.toNumber()).toBe(0);
+      expect(userPositionAccountData.yesShares.toNumber()).toBe(0);
+      expect(userPositionAccountData.user.toString()).toBe(
+        user.publicKey.toString()
+      );
+      expect(userPositionAccountData.bump).toBe(
+        pdaHelper.userPositionBump(marketPDA, user.publicKey)
+      );
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("create an user for non-existing market", async () => {
+    const marketPDA = pdaHelper.market(randomMarketId());
+    const userPositionPDA = pdaHelper.userPosition(marketPDA, user.publicKey);
+
+    try {
+      await program.methods
+        .createUser()
+        .accounts({
+          market: marketPDA,
+          user: user.publicKey,
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorCode.number === 3012) {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the market wasn't yet created");
+  });
+
+  test("place a bet", async () => {
+    const amount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+    const yesChoice = true;
+    const noChoice = false;
+
+    try {
+      const signature = await program.methods
+        .placeBet(amount, yesChoice)
+        .accounts({
+          market: marketPDA,
+          config: configPDA,
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      computeUnits.placeBet = await fetchComputeUnits(program, signature);
+
+      await program.methods
+        .placeBet(amount, noChoice)
+        .accounts({
+          market: marketPDA,
+          config: configPDA,
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const [event] = await fetchEvents(program, signature);
+      expect(event.name).toBe("BetPlaced");
+      expect(event.data.user.toString()).toBe(user.publicKey.toString());
+      expect(event.data.choice).toBe(yesChoice);
+      expect(event.data.amount.toNumber()).toBe(amount.toNumber());
+      expect(event.data.shares.toNumber()).toBe(
+        calculateShares(amount.toNumber())
+      );
+      expect(event.data.referrer).toBeNull();
+
+      const userPositionAccountData = await program.account.userPosition.fetch(
+        userPositionPDA
+      );
+
+      expect(userPositionAccountData.claimed).toBe(false);
+      expect(userPositionAccountData.market.toString()).toBe(
+        marketPDA.toString()
+      );
+      expect(userPositionAccountData.noShares.toNumber()).toBe(
+        calculateShares(amount.toNumber())
+      );
+      expect(userPositionAccountData.yesShares.toNumber()).toBe(
+        calculateShares(amount.toNumber())
+      );
+      expect(userPositionAccountData.depositedYes.toNumber()).toBe(
+        amount.toNumber()
+      );
+      expect(userPositionAccountData.depositedNo.toNumber()).toBe(
+        amount.toNumber()
+      );
+
+      const userProfileAccountData = await program.account.userProfile.fetch(
+        pdaHelper.userProfile(user.publicKey)
+      );
+      expect(userProfileAccountData.user.toString()).toBe(
+        user.publicKey.toString()
+      );
+      expect(userProfileAccountData.totalVolume.toNumber()).toBe(
+        2 * amount.toNumber()
+      );
+      expect(userProfileAccountData.marketsEntered).toBe(1);
+      expect(userPositionAccountData.user.toString()).toBe(
+        user.publicKey.toString()
+      );
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("set referral code", async () => {
+    try {
+      await program.methods
+        .setReferralCode("BLINK42")
+        .accounts({
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+
+      const userProfileAccountData = await program.account.userProfile.fetch(
+        pdaHelper.userProfile(user.publicKey)
+      );
+      expect(userProfileAccountData.referralCode).toBe("BLINK42");
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("set an invalid referral code", async () => {
+    try {
+      await program.methods
+        .setReferralCode("blink-42")
+        .accounts({
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid referral code") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the referral code is invalid");
+  });
+
+  test("place a bet lower than 90k lamports", async () => {
+    const amount = new anchor.BN(90_000);
+    const yesChoice = true;
+
+    try {
+      await program.methods
+        .placeBet(amount, yesChoice)
+        .accounts({
+          market: marketPDA,
+          config: configPDA,
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Bet amount is too low") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("test should have failed as the bet amount is too low");
+  });
+
+  test("place a bet with insufficient lamports", async () => {
+    const amount = new anchor.BN(10 * LAMPORTS_PER_SOL);
+    const yesChoice = true;
+
+    try {
+      await program.methods
+        .placeBet(amount, yesChoice)
+        .accounts({
+          market: marketPDA,
+          config: configPDA,
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Insufficient user funds") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail(
+      "test should have failed as the user has insufficient funds to bet"
+    );
+  });
+
+  test("place a bet with a referrer", async () => {
+    const amount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+    referredUserPositionPDA = pdaHelper.userPosition(
+      marketPDA,
+      referredUser.publicKey
+    );
+    referralPDA = pdaHelper.referral(referredUserPositionPDA);
+    betReceiptPDA = pdaHelper.betReceipt(
+      referredUserPositionPDA,
+      new anchor.BN(0)
+    );
+
+    try {
+      const signature = await connection.requestAirdrop(
+        referredUser.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(signature);
+
+      await program.methods
+        .createUser()
+        .accounts({
+          market: marketPDA,
+          user: referredUser.publicKey,
+          userPosition: referredUserPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([referredUser])
+        .rpc();
+
+      await program.methods
+        .placeBet(amount, true)
+        .accounts({
+          market: marketPDA,
+          config: configPDA,
+          user: referredUser.publicKey,
+          userProfile: pdaHelper.userProfile(referredUser.publicKey),
+          userPosition: referredUserPositionPDA,
+          systemProgram: SystemProgram.programId,
+          referrer: referrer.publicKey,
+          referral: referralPDA,
+          betReceipt: betReceiptPDA,
+        })
+        .signers([referredUser])
+        .rpc();
+
+      const referralAccountData = await program.account.referral.fetch(
+        referralPDA
+      );
+      expect(referralAccountData.referrer.toString()).toBe(
+        referrer.publicKey.toString()
+      );
+      expect(referralAccountData.userPosition.toString()).toBe(
+        referredUserPositionPDA.toString()
+      );
+      expect(referralAccountData.volume.toNumber()).toBe(amount.toNumber());
+      expect(referralAccountData.claimed).toBe(false);
+
+      const userPositionAccountData = await program.account.userPosition.fetch(
+        referredUserPositionPDA
+      );
+      expect(userPositionAccountData.referred).toBe(true);
+      expect(userPositionAccountData.betCount.toNumber()).toBe(1);
+
+      const betReceiptAccountData = await program.account.betReceipt.fetch(
+        betReceiptPDA
+      );
+      expect(betReceiptAccountData.sequence.toNumber()).toBe(0);
+      expect(betReceiptAccountData.outcome).toBe(true);
+      expect(betReceiptAccountData.amount.toNumber()).toBe(amount.toNumber());
+      expect(betReceiptAccountData.shares.toNumber()).toBe(
+        calculateShares(amount.toNumber())
+      );
+
+      const marketAccountData = await program.account.market.fetch(marketPDA);
+      expect(marketAccountData.referredVolume.toNumber()).toBe(
+        amount.toNumber()
+      );
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("place a bet while the program is paused", async () => {
+    await program.methods
+      .pauseProgram({ oracleManipulation: {} }, true)
+      .accounts({
+        guardianRole: pdaHelper.role(ROLE.guardian),
+        guardian: authority,
+        config: configPDA,
+      })
+      .rpc();
+
+    const configAccountData = await program.account.config.fetch(configPDA);
+    expect(configAccountData.pause.paused).toBe(true);
+    expect(configAccountData.pause.reason).toEqual({ oracleManipulation: {} });
+
+    try {
+      await program.methods
+        .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), true)
+        .accounts({
+          market: marketPDA,
+          config: configPDA,
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Program is paused") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    } finally {
+      await program.methods
+        .resumeProgram()
+        .accounts({
+          guardianRole: pdaHelper.role(ROLE.guardian),
+          guardian: authority,
+          config: configPDA,
+        })
+        .rpc();
+    }
+
+    assert.fail("expected test to fail as the program is paused");
+  });
+
+  test("pause market with a wallet without the guardian role", async () => {
+    try {
+      await program.methods
+        .pauseMarket({ bugDiscovered: {} }, false)
+        .accounts({
+          guardianRole: pdaHelper.role(ROLE.guardian, user.publicKey),
+          guardian: user.publicKey,
+          market: marketPDA,
+          config: configPDA,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorCode.number === 3012) {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the signer has no guardian role");
+  });
+
+  test("migrate a position that already tracks cost basis", async () => {
+    try {
+      await program.methods
+        .migratePosition()
+        .accounts({
+          market: marketPDA,
+          userPosition: userPositionPDA,
+          user: user.publicKey,
+          payer: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Account has already been migrated") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the position is already migrated");
+  });
+
+  test("cancel bet", async () => {
+    try {
+      const signature = await program.methods
+        .cancelBet()
+        .accounts({
+          market: marketPDA,
+          config: configPDA,
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+          userPosition: userPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+
+      const [event] = await fetchEvents(program, signature);
+      expect(event.name).toBe("BetCancelled");
+      expect(event.data.user.toString()).toBe(user.publicKey.toString());
+      expect(event.data.yesShares.toNumber()).toBe(
+        calculateShares(0.1 * LAMPORTS_PER_SOL)
+      );
+      expect(event.data.refundAmount.toNumber()).toBeGreaterThan(0);
+
+      const userPositionAccountData = await program.account.userPosition.fetch(
+        userPositionPDA
+      );
+
+      expect(userPositionAccountData.claimed).toBe(false);
+      expect(userPositionAccountData.market.toString()).toBe(
+        marketPDA.toString()
+      );
+      expect(userPositionAccountData.noShares.toNumber()).toBe(0);
+      expect(userPositionAccountData.yesShares.toNumber()).toBe(0);
+      expect(userPositionAccountData.depositedYes.toNumber()).toBe(0);
+      expect(userPositionAccountData.depositedNo.toNumber()).toBe(0);
+      expect(userPositionAccountData.refunded.eq(event.data.refundAmount)).toBe(
+        true
+      );
+      expect(userPositionAccountData.user.toString()).toBe(
+        user.publicKey.toString()
+      );
+
+      const userProfileAccountData = await program.account.userProfile.fetch(
+        pdaHelper.userProfile(user.publicKey)
+      );
+      expect(userProfileAccountData.realizedPnl.toNumber()).toBe(
+        -(2 * calculateShares(0.1 * LAMPORTS_PER_SOL) -
+          event.data.refundAmount.toNumber())
+      );
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("close bet receipt before the market is resolved", async () => {
+    try {
+      await program.methods
+        .closeBetReceipt()
+        .accounts({
+          market: marketPDA,
+          userPosition: referredUserPositionPDA,
+          betReceipt: betReceiptPDA,
+          user: referredUser.publicKey,
+        })
+        .signers([referredUser])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "The market is not resolved yet.") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the market is not resolved");
+  });
+
+  test("resolve market", async () => {
+    try {
+      await waitForExpiry(program, marketPDA);
+
+      const signature = await program.methods
+        .resolveMarket()
+        .accounts({
+          resolverRole: pdaHelper.role(ROLE.resolver),
+          authority,
+          market: marketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      const marketAccountData = await program.account.market.fetch(marketPDA);
+      expect(marketAccountData.status).toBe(MARKET_STATUS.resolved);
+      expect(marketAccountData.winningOutcome).not.toBe(MARKET_OUTCOME.none);
+      expect(marketAccountData.resolver.toString()).toBe(authority.toString());
+
+      const [event] = await fetchEvents(program, signature);
+      expect(event.name).toBe("MarketResolved");
+      expect(event.data.finalPrice.eq(marketAccountData.finalPrice)).toBe(true);
+      expect(
+        event.data.winningOutcome ? MARKET_OUTCOME.yes : MARKET_OUTCOME.no
+      ).toBe(marketAccountData.winningOutcome);
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("claim winnings", async () => {
+    try {
+      const signature = await program.methods
+        .claimWinnings()
+        .accounts({
+          user: user.publicKey,
+          userProfile: pdaHelper.userProfile(user.publicKey),
+          userPosition: userPositionPDA,
+          market: marketPDA,
+          config: configPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      computeUnits.claimWinnings = await fetchComputeUnits(program, signature);
+
+      const userPositionAccountData = await program.account.userPosition.fetch(
+        userPositionPDA
+      );
+      expect(userPositionAccountData.claimed).toBe(true);
+      expect(userPositionAccountData.yesShares.toNumber()).toBe(0);
+      expect(userPositionAccountData.noShares.toNumber()).toBe(0);
+
+      const [event] = await fetchEvents(program, signature);
+      expect(event.name).toBe("WinningsClaimed");
+      expect(event.data.user.toString()).toBe(user.publicKey.toString());
+      expect(event.data.refund).toBe(false);
+      expect(userPositionAccountData.claimedAmount.eq(event.data.amount)).toBe(
+        true
+      );
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  // The Borsh baseline is recorded by scripts/record-borsh-compute-units.sh
+  // from the commit before Market went zero-copy. The zero-copy paths must
+  // never cost more than Borsh did.
+  test("benchmark compute units of the hot paths", () => {
+    console.table(computeUnits);
+
+    expect(computeUnits.placeBet).toBeGreaterThan(0);
+    expect(computeUnits.placeBet).toBeLessThan(200_000);
+    expect(computeUnits.claimWinnings).toBeGreaterThan(0);
+    expect(computeUnits.claimWinnings).toBeLessThan(200_000);
+
+    expect(fs.existsSync(borshComputeUnitsPath)).toBe(true);
+    const borshComputeUnits = JSON.parse(
+      fs.readFileSync(borshComputeUnitsPath, "utf8")
+    );
+    console.table({ borsh: borshComputeUnits, zeroCopy: computeUnits });
+    expect(computeUnits.placeBet).toBeLessThanOrEqual(
+      borshComputeUnits.placeBet
+    );
+    expect(computeUnits.claimWinnings).toBeLessThanOrEqual(
+      borshComputeUnits.claimWinnings
+    );
+  });
+
+  test("close bet receipt", async () => {
+    try {
+      await program.methods
+        .closeBetReceipt()
+        .accounts({
+          market: marketPDA,
+          userPosition: referredUserPositionPDA,
+          betReceipt: betReceiptPDA,
+          user: referredUser.publicKey,
+        })
+        .signers([referredUser])
+        .rpc();
+
+      const betReceiptAccountInfo = await connection.getAccountInfo(
+        betReceiptPDA
+      );
+      expect(betReceiptAccountInfo).toBeNull();
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("refund creator bond", async () => {
+    try {
+      await program.methods
+        .refundCreatorBond()
+        .accounts({
+          authority,
+          market: marketPDA,
+        })
+        .rpc();
+
+      const marketAccountData = await program.account.market.fetch(marketPDA);
+      expect(marketAccountData.creatorBond.toNumber()).toBe(0);
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("claim referral rewards", async () => {
+    const volume = 0.1 * LAMPORTS_PER_SOL;
+    const expectedReward = Math.floor(
+      (volume * FEE_SCHEDULE.referrerBps) / 10_000
+    );
+
+    try {
+      const signature = await connection.requestAirdrop(
+        referrer.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(signature);
+      const balanceBefore = await connection.getBalance(referrer.publicKey);
+
+      await program.methods
+        .claimReferralRewards()
+        .accounts({
+          referrer: referrer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: marketPDA, isSigner: false, isWritable: true },
+          { pubkey: referralPDA, isSigner: false, isWritable: true },
+        ])
+        .signers([referrer])
+        .rpc();
+
+      const referralAccountData = await program.account.referral.fetch(
+        referralPDA
+      );
+      expect(referralAccountData.claimed).toBe(true);
+
+      const balanceAfter = await connection.getBalance(referrer.publicKey);
+      expect(balanceAfter - balanceBefore).toBe(expectedReward - 5000);
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+  });
+
+  test("update fee tiers with unsorted volume thresholds", async () => {
+    const feeTiers = [
+      { minVolume: new anchor.BN(2 * LAMPORTS_PER_SOL), discountBps: 2_000 },
+      { minVolume: new anchor.BN(LAMPORTS_PER_SOL), discountBps: 5_000 },
+    ];
+
+    try {
+      const proposalPDA = await queueProposal({
+        updateFeeTiers: { feeTiers },
+      });
+
+      await program.methods
+        .updateFeeTiers(feeTiers)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid fee tiers") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the fee tiers are not sorted");
+  });
+
+  test("place bets beyond the market exposure limits", async () => {
+    const marketId = randomMarketId();
+    const limitedMarketPDA = pdaHelper.market(marketId);
+    const whale = Keypair.generate();
+    const counterparty = Keypair.generate();
+    const exposureLimits = {
+      maxBetPerTx: new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+      maxPositionPerUser: new anchor.BN(0.08 * LAMPORTS_PER_SOL),
+      maxMarketTvl: new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+      maxImbalanceBps: new anchor.BN(20_000),
+    };
+
+    const placeBet = async (bettor: Keypair, amount: number, choice: boolean) => {
+      await program.methods
+        .placeBet(new anchor.BN(amount * LAMPORTS_PER_SOL), choice)
+        .accounts({
+          market: limitedMarketPDA,
+          config: configPDA,
+          user: bettor.publicKey,
+          userProfile: pdaHelper.userProfile(bettor.publicKey),
+          userPosition: pdaHelper.userPosition(limitedMarketPDA, bettor.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+    };
+
+    const expectBetToFail = async (
+      bettor: Keypair,
+      amount: number,
+      choice: boolean,
+      errorMessage: string
+    ) => {
+      try {
+        await placeBet(bettor, amount, choice);
+      } catch (err) {
+        if (err instanceof AnchorError) {
+          expect(err.error.errorMessage).toBe(errorMessage);
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected error");
+      }
+
+      assert.fail(`expected bet to fail with "${errorMessage}"`);
+    };
+
+    await program.methods
+      .createMarket(marketId, new anchor.BN(60))
+      .accounts({
+        authority,
+        market: limitedMarketPDA,
+        config: configPDA,
+        priceFeedConfig: priceFeedConfigPDA,
+        priceFeed: feed,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const proposalPDA = await queueProposal({
+      updateMarketLimits: { market: limitedMarketPDA, exposureLimits },
+    });
+
+    await program.methods
+      .updateMarketLimits(exposureLimits)
+      .accounts({
+        adminRole: pdaHelper.role(ROLE.admin),
+        authority,
+        market: limitedMarketPDA,
+        proposal: proposalPDA,
+      })
+      .rpc();
+
+    for (const bettor of [whale, counterparty]) {
+      const signature = await connection.requestAirdrop(
+        bettor.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(signature);
+
+      await program.methods
+        .createUser()
+        .accounts({
+          market: limitedMarketPDA,
+          user: bettor.publicKey,
+          userPosition: pdaHelper.userPosition(limitedMarketPDA, bettor.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+    }
+
+    await expectBetToFail(
+      whale,
+      0.06,
+      true,
+      "Bet exceeds the maximum bet per transaction"
+    );
+
+    // the opposite side is empty, so the first bet is not held to the ratio
+    await placeBet(whale, 0.05, true);
+
+    await expectBetToFail(
+      whale,
+      0.04,
+      true,
+      "Bet exceeds the maximum position per user"
+    );
+
+    await placeBet(counterparty, 0.02, false);
+
+    await expectBetToFail(
+      counterparty,
+      0.03,
+      true,
+      "Bet exceeds the maximum imbalance between outcomes"
+    );
+    await expectBetToFail(
+      counterparty,
+      0.04,
+      false,
+      "Bet exceeds the maximum market TVL"
+    );
+
+    const marketAccountData = await program.account.market.fetch(
+      limitedMarketPDA
+    );
+    expect(marketAccountData.exposureLimits.maxMarketTvl.eq(exposureLimits.maxMarketTvl)).toBe(true);
+    expect(marketAccountData.totalYesShares.toNumber()).toBe(0.05 * LAMPORTS_PER_SOL);
+    expect(marketAccountData.totalNoShares.toNumber()).toBe(0.02 * LAMPORTS_PER_SOL);
+  });
+
+  test("challenge a resolution during the dispute window", async () => {
+    const marketId = randomMarketId();
+    const disputedMarketPDA = pdaHelper.market(marketId);
+    const disputePDA = pdaHelper.dispute(disputedMarketPDA);
+    const bettor = Keypair.generate();
+    const challenger = Keypair.generate();
+    const bettorPositionPDA = pdaHelper.userPosition(
+      disputedMarketPDA,
+      bettor.publicKey
+    );
+    const disputePolicy = { ...DISPUTE_POLICY, window: new anchor.BN(60 * 60) };
+
+    const updateDisputePolicy = async (policy: typeof DISPUTE_POLICY) => {
+      const proposalPDA = await queueProposal({
+        updateDisputePolicy: { disputePolicy: policy },
+      });
+
+      await program.methods
+        .updateDisputePolicy(policy)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+    };
+
+    const claimWinnings = () =>
+      program.methods
         .claimWinnings()
         .accounts({
-          user: user.publicKey,
-          userPosition: userPositionPDA,
+          user: bettor.publicKey,
+          userProfile: pdaHelper.userProfile(bettor.publicKey),
+          userPosition: bettorPositionPDA,
+          market: disputedMarketPDA,
+          config: configPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+
+    try {
+      await updateDisputePolicy(disputePolicy);
+
+      for (const wallet of [bettor, challenger]) {
+        const signature = await connection.requestAirdrop(
+          wallet.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await connection.confirmTransaction(signature);
+      }
+
+      await program.methods
+        .createMarket(marketId, new anchor.BN(60))
+        .accounts({
+          authority,
+          market: disputedMarketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .createUser()
+        .accounts({
+          market: disputedMarketPDA,
+          user: bettor.publicKey,
+          userPosition: bettorPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+
+      await program.methods
+        .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), true)
+        .accounts({
+          market: disputedMarketPDA,
+          config: configPDA,
+          user: bettor.publicKey,
+          userProfile: pdaHelper.userProfile(bettor.publicKey),
+          userPosition: bettorPositionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+
+      await waitForExpiry(program, disputedMarketPDA);
+
+      await program.methods
+        .resolveMarket()
+        .accounts({
+          resolverRole: pdaHelper.role(ROLE.resolver),
+          authority,
+          market: disputedMarketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+        })
+        .rpc();
+
+      try {
+        await claimWinnings();
+        assert.fail("expected claim to fail while the dispute window is open");
+      } catch (err) {
+        expect(err).toBeInstanceOf(AnchorError);
+        expect(err.error.errorMessage).toBe("Dispute window is still open");
+      }
+
+      // the local price feed never moves, so it still agrees with the
+      // resolution; the upheld path is covered by the program tests, which
+      // run against the mock oracle
+      const resolvedMarketData = await program.account.market.fetch(
+        disputedMarketPDA
+      );
+      expect(resolvedMarketData.winningOutcome).toBe(MARKET_OUTCOME.no);
+
+      try {
+        await program.methods
+          .challengeOutcome()
+          .accounts({
+            market: disputedMarketPDA,
+            dispute: disputePDA,
+            config: configPDA,
+            priceFeedConfig: priceFeedConfigPDA,
+            priceFeed: feed,
+            challenger: challenger.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([challenger])
+          .rpc();
+        assert.fail("expected the challenge to fail while the feed agrees");
+      } catch (err) {
+        expect(err).toBeInstanceOf(AnchorError);
+        expect(err.error.errorMessage).toBe(
+          "Challenge must propose a different outcome"
+        );
+      }
+      expect(await connection.getAccountInfo(disputePDA)).toBeNull();
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    } finally {
+      await updateDisputePolicy(DISPUTE_POLICY);
+    }
+  });
+
+  test("claim winnings at fee tier boundaries", async () => {
+    const tierThreshold = new anchor.BN(20_000_000);
+    const discountBps = 5_000;
+    const marketId = randomMarketId();
+    const tierMarketPDA = pdaHelper.market(marketId);
+    const atThreshold = Keypair.generate();
+    const belowThreshold = Keypair.generate();
+    const counterparty = Keypair.generate();
+
+    // the local price feed never moves, so "no" always wins
+    const bets: [Keypair, anchor.BN, boolean][] = [
+      [atThreshold, tierThreshold, false],
+      [belowThreshold, tierThreshold.subn(1), false],
+      [counterparty, new anchor.BN(10_000_000), true],
+    ];
+
+    try {
+      await program.methods
+        .createMarket(marketId, new anchor.BN(60))
+        .accounts({
+          authority,
+          market: tierMarketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      for (const [bettor, amount, choice] of bets) {
+        const signature = await connection.requestAirdrop(
+          bettor.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await connection.confirmTransaction(signature);
+
+        const bettorPositionPDA = pdaHelper.userPosition(
+          tierMarketPDA,
+          bettor.publicKey
+        );
+
+        await program.methods
+          .createUser()
+          .accounts({
+            market: tierMarketPDA,
+            user: bettor.publicKey,
+            userPosition: bettorPositionPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bettor])
+          .rpc();
+
+        await program.methods
+          .placeBet(amount, choice)
+          .accounts({
+            market: tierMarketPDA,
+            config: configPDA,
+            user: bettor.publicKey,
+            userProfile: pdaHelper.userProfile(bettor.publicKey),
+            userPosition: bettorPositionPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bettor])
+          .rpc();
+      }
+
+      const feeTiers = [{ minVolume: tierThreshold, discountBps }];
+      const proposalPDA = await queueProposal({
+        updateFeeTiers: { feeTiers },
+      });
+
+      await program.methods
+        .updateFeeTiers(feeTiers)
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+
+      await waitForExpiry(program, tierMarketPDA);
+
+      await program.methods
+        .resolveMarket()
+        .accounts({
+          resolverRole: pdaHelper.role(ROLE.resolver),
+          authority,
+          market: tierMarketPDA,
+          config: configPDA,
+          priceFeedConfig: priceFeedConfigPDA,
+          priceFeed: feed,
+        })
+        .rpc();
+
+      const pool = bets.reduce((sum, [, amount]) => sum.add(amount), new anchor.BN(0));
+      const totalWinningShares = tierThreshold.add(tierThreshold.subn(1));
+      const totalFees = calculateTotalFees(pool, new anchor.BN(0), FEE_SCHEDULE);
+
+      for (const [bettor, amount, expectDiscount] of [
+        [atThreshold, tierThreshold, true],
+        [belowThreshold, tierThreshold.subn(1), false],
+      ] as [Keypair, anchor.BN, boolean][]) {
+        const signature = await program.methods
+          .claimWinnings()
+          .accounts({
+            user: bettor.publicKey,
+            userProfile: pdaHelper.userProfile(bettor.publicKey),
+            userPosition: pdaHelper.userPosition(tierMarketPDA, bettor.publicKey),
+            market: tierMarketPDA,
+            config: configPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bettor])
+          .rpc({ commitment: "confirmed" });
+
+        const [event] = await fetchEvents(program, signature);
+        const expectedRebate = expectDiscount
+          ? calculateFeeRebate(
+              amount,
+              totalWinningShares,
+              pool,
+              totalFees,
+              discountBps
+            )
+          : new anchor.BN(0);
+
+        expect(event.name).toBe("WinningsClaimed");
+        expect(event.data.feeRebate.toString()).toBe(expectedRebate.toString());
+      }
+    } catch (err) {
+      console.log(err);
+      assert.fail("unexpected error");
+    } finally {
+      const proposalPDA = await queueProposal({
+        updateFeeTiers: { feeTiers: [] },
+      });
+
+      await program.methods
+        .updateFeeTiers([])
+        .accounts({
+          adminRole: pdaHelper.role(ROLE.admin),
+          authority,
+          config: configPDA,
+          proposal: proposalPDA,
+        })
+        .rpc();
+    }
+  });
+
+  test("withdraw team fee before the timelock expires", async () => {
+    const marketAccountData = await program.account.market.fetch(marketPDA);
+    expect(marketAccountData.status).toBe(MARKET_STATUS.resolved);
+    expect(marketAccountData.teamFeePaid).toBe(0);
+
+    try {
+      await program.methods
+        .withdrawTeamFee()
+        .accounts({
+          treasuryRole: pdaHelper.role(ROLE.treasury),
+          authority,
           market: marketPDA,
+          teamWallet: TEAM_WALLET,
           systemProgram: SystemProgram.programId,
         })
-        .signers([user])
         .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Team fee timelock has not expired") {
+          assert.ok("test failed as expected");
+          return;
+        }
 
-      const userPositionAccountData = await program.account.userPosition.fetch(
-        userPositionPDA
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the team fee is still timelocked");
+  });
+
+  test("batch withdraw team fees including an unresolved market", async () => {
+    const marketId = randomMarketId();
+    const unresolvedMarketPDA = pdaHelper.market(marketId);
+
+    await program.methods
+      .createMarket(marketId, new anchor.BN(60))
+      .accounts({
+        authority,
+        market: unresolvedMarketPDA,
+        config: configPDA,
+        priceFeedConfig: priceFeedConfigPDA,
+        priceFeed: feed,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .withdrawTeamFees()
+        .accounts({
+          treasuryRole: pdaHelper.role(ROLE.treasury),
+          authority,
+          teamWallet: TEAM_WALLET,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: unresolvedMarketPDA, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "The market is not resolved yet.") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the market is not resolved");
+  });
+
+  test("migrate a v0 price feed fixture", async () => {
+    await expect(
+      program.account.priceFeedConfig.fetch(LEGACY_FIXTURES.priceFeedConfig)
+    ).rejects.toThrow();
+
+    await program.methods
+      .migratePriceFeed()
+      .accounts({
+        priceFeedConfig: LEGACY_FIXTURES.priceFeedConfig,
+        payer: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const priceFeedConfigAccountData =
+      await program.account.priceFeedConfig.fetch(
+        LEGACY_FIXTURES.priceFeedConfig
       );
-      expect(userPositionAccountData.claimed).toBe(true);
-      expect(userPositionAccountData.yesShares.toNumber()).toBe(0);
-      expect(userPositionAccountData.noShares.toNumber()).toBe(0);
+    expect(priceFeedConfigAccountData.version).toBe(2);
+    expect(priceFeedConfigAccountData.bump).toBe(
+      pdaHelper.priceFeedConfigBump(LEGACY_FIXTURES.priceFeed)
+    );
+    expect(priceFeedConfigAccountData.priceFeed.toString()).toBe(
+      LEGACY_FIXTURES.priceFeed.toString()
+    );
+    // the first release kept no parameters, so the feed waits for an admin
+    expect(priceFeedConfigAccountData.symbol).toBe("");
+    expect(priceFeedConfigAccountData.maxStaleness.toNumber()).toBe(300);
+    expect(priceFeedConfigAccountData.enabled).toBe(false);
+    expect(priceFeedConfigAccountData.openMarkets.toNumber()).toBe(0);
+  });
+
+  test("enable a migrated price feed that has no symbol yet", async () => {
+    try {
+      await enablePriceFeed(
+        LEGACY_FIXTURES.priceFeed,
+        LEGACY_FIXTURES.priceFeedConfig
+      );
+    } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Invalid symbol") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
+      console.log(err);
+      assert.fail("unexpected error");
+    }
+
+    assert.fail("expected test to fail as the feed has no symbol");
+  });
+
+  test("migrate a v0 market fixture", async () => {
+    await expect(
+      program.account.market.fetch(LEGACY_FIXTURES.market)
+    ).rejects.toThrow();
+
+    const signature = await program.methods
+      .migrateMarket()
+      .accounts({
+        market: LEGACY_FIXTURES.market,
+        priceFeedConfig: LEGACY_FIXTURES.priceFeedConfig,
+        payer: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const [event] = await fetchEvents(program, signature);
+    expect(event.name).toBe("MarketMigrated");
+    expect(event.data.version).toBe(4);
+
+    const accountInfo = await connection.getAccountInfo(LEGACY_FIXTURES.market);
+    expect(accountInfo.data.length).toBe(program.account.market.size);
+
+    const marketAccountData = await program.account.market.fetch(
+      LEGACY_FIXTURES.market
+    );
+    expect(marketAccountData.version).toBe(4);
+    expect(marketAccountData.symbolSeeded).toBe(1);
+    expect(marketAccountData.bump).toBe(
+      pdaHelper.symbolMarketBump("BONK", marketAccountData.authority)
+    );
+    expect(marketAccountData.status).toBe(MARKET_STATUS.open);
+    expect(decodeSymbol(marketAccountData.memecoinSymbol)).toBe("BONK");
+    expect(marketAccountData.feedId.toString()).toBe(
+      LEGACY_FIXTURES.priceFeed.toString()
+    );
+    expect(marketAccountData.marketId.toNumber()).toBe(0);
+    expect(marketAccountData.duration.toNumber()).toBe(3600);
+    expect(marketAccountData.totalYesShares.toNumber()).toBe(500_000_000);
+    expect(marketAccountData.totalNoShares.toNumber()).toBe(300_000_000);
+    expect(marketAccountData.initialPrice.toNumber()).toBe(123456);
+    expect(marketAccountData.finalPrice.toNumber()).toBe(0);
+    // the first release kept a flat 5% for the team
+    expect(marketAccountData.feeSchedule.protocolBps).toBe(500);
+    expect(marketAccountData.feeSchedule.creatorBps).toBe(0);
+    expect(marketAccountData.reserved.every((byte) => byte === 0)).toBe(true);
+
+    const priceFeedConfigAccountData =
+      await program.account.priceFeedConfig.fetch(
+        LEGACY_FIXTURES.priceFeedConfig
+      );
+    expect(priceFeedConfigAccountData.openMarkets.toNumber()).toBe(1);
+  });
+
+  test("migrate an already migrated market", async () => {
+    try {
+      await program.methods
+        .migrateMarket()
+        .accounts({
+          market: LEGACY_FIXTURES.market,
+          priceFeedConfig: LEGACY_FIXTURES.priceFeedConfig,
+          payer: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
     } catch (err) {
+      if (err instanceof AnchorError) {
+        if (err.error.errorMessage === "Account has already been migrated") {
+          assert.ok("test failed as expected");
+          return;
+        }
+
+        console.log(err);
+        assert.fail("unexpected anchor error");
+      }
+
       console.log(err);
       assert.fail("unexpected error");
     }
+
+    assert.fail("expected test to fail as the market is already migrated");
   });
 
-  test.skip("withdraw team fees");
+  test("migrate a v0 position fixture", async () => {
+    await program.methods
+      .migratePosition()
+      .accounts({
+        market: LEGACY_FIXTURES.market,
+        userPosition: LEGACY_FIXTURES.userPositionV0,
+        user: LEGACY_FIXTURES.userV0,
+        payer: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const accountInfo = await connection.getAccountInfo(
+      LEGACY_FIXTURES.userPositionV0
+    );
+    expect(accountInfo.data.length).toBe(program.account.userPosition.size);
+
+    const userPositionAccountData = await program.account.userPosition.fetch(
+      LEGACY_FIXTURES.userPositionV0
+    );
+    expect(userPositionAccountData.version).toBe(3);
+    expect(userPositionAccountData.user.toString()).toBe(
+      LEGACY_FIXTURES.userV0.toString()
+    );
+    expect(userPositionAccountData.bump).toBe(
+      pdaHelper.userPositionBump(LEGACY_FIXTURES.market, LEGACY_FIXTURES.userV0)
+    );
+    expect(userPositionAccountData.yesShares.toNumber()).toBe(200_000_000);
+    expect(userPositionAccountData.noShares.toNumber()).toBe(100_000_000);
+    expect(userPositionAccountData.claimed).toBe(false);
+    expect(userPositionAccountData.referred).toBe(false);
+    expect(userPositionAccountData.betCount.toNumber()).toBe(0);
+    // shares were minted one per lamport, so they become the cost basis
+    expect(userPositionAccountData.depositedYes.toNumber()).toBe(200_000_000);
+    expect(userPositionAccountData.depositedNo.toNumber()).toBe(100_000_000);
+    expect(userPositionAccountData.refunded.toNumber()).toBe(0);
+  });
 });
@@ -1,5 +1,86 @@
+import { BN } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 
 export const TEAM_WALLET = new PublicKey(
   "GerW59qscGWPJarbe8Px3sUVEXJ269Z9RQndYc9MWxCe"
 );
+
+// mirrors the on-chain Role enum, whose discriminant is part of the role PDA seeds
+export const ROLE = {
+  admin: 0,
+  marketCreator: 1,
+  resolver: 2,
+  guardian: 3,
+  treasury: 4,
+  arbiter: 5,
+};
+
+export const CREATION_BOND = 100_000_000;
+
+// proposals are executable as soon as they are queued
+export const GOVERNANCE_DELAY = 0;
+
+// markets are unlimited by default; the exposure tests set per-market limits
+export const EXPOSURE_LIMITS = {
+  maxBetPerTx: new BN(0),
+  maxPositionPerUser: new BN(0),
+  maxMarketTvl: new BN(0),
+  maxImbalanceBps: new BN(0),
+};
+
+// resolutions are final immediately unless a test opens a dispute window
+export const DISPUTE_POLICY = {
+  window: new BN(0),
+  bond: new BN(100_000_000),
+};
+
+export const FEE_SCHEDULE = {
+  protocolBps: 300,
+  creatorBps: 100,
+  referrerBps: 50,
+  resolverBps: 50,
+};
+
+// the cloned devnet feed is not updated on the local validator, so staleness
+// is effectively disabled for tests
+export const PRICE_FEED_PARAMS = {
+  symbol: "SOL",
+  decimals: 8,
+  oracleSource: { pyth: {} },
+  maxStaleness: new BN(10 * 365 * 24 * 60 * 60),
+  maxConfidenceBps: 10_000,
+};
+
+// Market is zero-copy, so enums and options are stored as bytes
+export const MARKET_STATUS = {
+  open: 0,
+  resolved: 1,
+  voided: 2,
+};
+
+export const MARKET_OUTCOME = {
+  none: 0,
+  no: 1,
+  yes: 2,
+};
+
+export const MARKET_VOID_REASON = {
+  none: 0,
+  creatorMisconfiguration: 1,
+  oracleFailure: 2,
+};
+
+// accounts the first release wrote, loaded into the local validator in their
+// pre-versioning layouts, see the [[test.validator.account]] entries in
+// Anchor.toml
+export const LEGACY_FIXTURES = {
+  // seeded by its symbol, with the feed registration below as its feed id
+  market: new PublicKey("6ketQrPrP6csKfRemVYPLXmzB8zQtCZ8eXnNY6poZisK"),
+  priceFeedConfig: new PublicKey(
+    "HR5VUgmiUnh8vfNdwzKXRPZZsCzuDj4TJZ8F19YYLwkS"
+  ),
+  priceFeed: new PublicKey("62MyEqvd6t5EFAwH3pB29tG4Ey2w8Pd1PRthtUZuNVeq"),
+  // a bettor's position on the fixture market
+  userPositionV0: new PublicKey("8bucrib38utxthxo8WpCaysgTVG1FybxvDRsCy9RbbkV"),
+  userV0: new PublicKey("F5k2Qx5FRbEQT1cmnFWxRUVv8Rv9Km8ZyYExBgRFtLUW"),
+};
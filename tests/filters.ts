@@ -0,0 +1,66 @@
+import { Program, utils } from "@coral-xyz/anchor";
+import { GetProgramAccountsFilter, PublicKey } from "@solana/web3.js";
+
+import { MARKET_STATUS } from "./constants";
+import { BlinkTake2 } from "../target/types/blink_take_2";
+
+// mirrors the MARKET_*_OFFSET constants in state/market.rs, which are checked
+// against the struct at compile time, and these against the IDL in the tests
+export const MARKET_OFFSETS = {
+  version: 8,
+  status: 9,
+  authority: 16,
+  feedId: 48,
+  symbol: 80,
+  marketId: 96,
+  startTime: 104,
+  duration: 112,
+};
+
+export type MarketFilterOptions = {
+  status?: keyof typeof MARKET_STATUS;
+  authority?: PublicKey;
+  feedId?: PublicKey;
+  symbol?: string;
+};
+
+// Builds getProgramAccounts filters matching current-layout markets. Time
+// ranges such as "ending before T" cannot be expressed as memcmp filters, so
+// callers compare start_time + duration on the returned accounts.
+export const marketFilters = (
+  program: Program<BlinkTake2>,
+  options: MarketFilterOptions = {}
+): GetProgramAccountsFilter[] => {
+  const memcmp = (offset: number, bytes: Buffer): GetProgramAccountsFilter => ({
+    memcmp: { offset, bytes: utils.bytes.bs58.encode(bytes) },
+  });
+
+  const filters: GetProgramAccountsFilter[] = [
+    { dataSize: program.account.market.size },
+  ];
+
+  if (options.status !== undefined) {
+    filters.push(
+      memcmp(
+        MARKET_OFFSETS.status,
+        Buffer.from([MARKET_STATUS[options.status]])
+      )
+    );
+  }
+
+  if (options.authority) {
+    filters.push(memcmp(MARKET_OFFSETS.authority, options.authority.toBuffer()));
+  }
+
+  if (options.feedId) {
+    filters.push(memcmp(MARKET_OFFSETS.feedId, options.feedId.toBuffer()));
+  }
+
+  if (options.symbol) {
+    const symbol = Buffer.alloc(16);
+    symbol.write(options.symbol);
+    filters.push(memcmp(MARKET_OFFSETS.symbol, symbol));
+  }
+
+  return filters;
+};
@@ -1,4 +1,4 @@
-import { AnchorProvider, Program } from "@coral-xyz/anchor";
+import { AnchorProvider, BN, Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 
 import { BlinkTake2 } from "../target/types/blink_take_2";
@@ -12,8 +12,101 @@ export class PDAHelper {
     this.program = program;
   }
 
+  config() {
+    let [pda, _] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      this.program.programId
+    );
+    return pda;
+  }
+
   priceFeedConfig(feed: PublicKey) {
+    return this.priceFeedConfigAddress(feed)[0];
+  }
+
+  priceFeedConfigBump(feed: PublicKey) {
+    return this.priceFeedConfigAddress(feed)[1];
+  }
+
+  market(marketId: BN, creator = this.provider.wallet.publicKey) {
+    return this.marketAddress(marketId, creator)[0];
+  }
+
+  marketBump(marketId: BN, creator = this.provider.wallet.publicKey) {
+    return this.marketAddress(marketId, creator)[1];
+  }
+
+  // markets created before market ids were seeded by their symbol instead
+  symbolMarketBump(symbol: string, creator: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), creator.toBuffer(), Buffer.from(symbol)],
+      this.program.programId
+    )[1];
+  }
+
+  userPosition(marketPDA: PublicKey, user: PublicKey) {
+    return this.userPositionAddress(marketPDA, user)[0];
+  }
+
+  userPositionBump(marketPDA: PublicKey, user: PublicKey) {
+    return this.userPositionAddress(marketPDA, user)[1];
+  }
+
+  referral(userPositionPDA: PublicKey) {
+    let [pda, _] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referral"), userPositionPDA.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  betReceipt(userPositionPDA: PublicKey, sequence: BN) {
+    let [pda, _] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bet_receipt"),
+        userPositionPDA.toBuffer(),
+        sequence.toArrayLike(Buffer, "le", 8),
+      ],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  userProfile(user: PublicKey) {
+    let [pda, _] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), user.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  role(role: number, member = this.provider.wallet.publicKey) {
     let [pda, _] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([role]), member.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  proposal(proposalId: BN) {
+    let [pda, _] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), proposalId.toArrayLike(Buffer, "le", 8)],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  dispute(marketPDA: PublicKey) {
+    let [pda, _] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), marketPDA.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  // accounts that store their bump are checked against these
+  private priceFeedConfigAddress(feed: PublicKey) {
+    return PublicKey.findProgramAddressSync(
       [
         Buffer.from("price_feed_config"),
         this.provider.wallet.publicKey.toBuffer(),
@@ -21,26 +114,23 @@ export class PDAHelper {
       ],
       this.program.programId
     );
-    return pda;
   }
 
-  market(coinSymbol: string) {
-    let [pda, _] = PublicKey.findProgramAddressSync(
+  private marketAddress(marketId: BN, creator: PublicKey) {
+    return PublicKey.findProgramAddressSync(
       [
         Buffer.from("market"),
-        this.provider.wallet.publicKey.toBuffer(),
-        Buffer.from(coinSymbol),
+        creator.toBuffer(),
+        marketId.toArrayLike(Buffer, "le", 8),
       ],
       this.program.programId
     );
-    return pda;
   }
 
-  userPosition(marketPDA: PublicKey, user: PublicKey) {
-    let [pda, _] = PublicKey.findProgramAddressSync(
+  private userPositionAddress(marketPDA: PublicKey, user: PublicKey) {
+    return PublicKey.findProgramAddressSync(
       [Buffer.from("user_position"), marketPDA.toBuffer(), user.toBuffer()],
       this.program.programId
     );
-    return pda;
   }
 }
@@ -1,3 +1,149 @@
+import { BN, EventParser, Idl, IdlType, Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import crypto from "node:crypto";
+
 export const calculateShares = (amount: number) => {
   return amount;
 };
+
+export const randomMarketId = () => {
+  return new BN(crypto.randomBytes(6).toString("hex"), 16);
+};
+
+export const randomProposalId = () => {
+  return new BN(crypto.randomBytes(6).toString("hex"), 16);
+};
+
+// markets store their symbol zero-padded to 16 bytes
+export const decodeSymbol = (bytes: number[]) => {
+  return Buffer.from(bytes).toString("utf8").replace(/\0+$/, "");
+};
+
+const PRIMITIVE_SIZES: Record<string, number> = {
+  bool: 1,
+  u8: 1,
+  i8: 1,
+  u16: 2,
+  i16: 2,
+  u32: 4,
+  i32: 4,
+  u64: 8,
+  i64: 8,
+  u128: 16,
+  i128: 16,
+  publicKey: 32,
+};
+
+// byte offsets of a zero-copy account's fields, discriminator included, as
+// laid out by the IDL. Only exact for accounts whose fields leave no padding.
+export const zeroCopyFieldOffsets = (idl: Idl, account: string) => {
+  const typeDefs = [...(idl.accounts ?? []), ...(idl.types ?? [])];
+  const fieldsOf = (name: string) => {
+    const typeDef = typeDefs.find((typeDef) => typeDef.name === name);
+    if (typeDef?.type.kind !== "struct") {
+      throw new Error(`${name} is not a struct`);
+    }
+    return typeDef.type.fields;
+  };
+  const sizeOf = (type: IdlType): number => {
+    if (typeof type === "string" && type in PRIMITIVE_SIZES) {
+      return PRIMITIVE_SIZES[type];
+    }
+    if (typeof type === "object" && "array" in type) {
+      return sizeOf(type.array[0]) * type.array[1];
+    }
+    if (typeof type === "object" && "defined" in type) {
+      return fieldsOf(type.defined).reduce(
+        (size, field) => size + sizeOf(field.type),
+        0
+      );
+    }
+    throw new Error(`${JSON.stringify(type)} has no fixed size`);
+  };
+
+  const offsets: Record<string, number> = {};
+  let offset = 8;
+  for (const field of fieldsOf(account)) {
+    offsets[field.name] = offset;
+    offset += sizeOf(field.type);
+  }
+  return offsets;
+};
+
+export const fetchEvents = async (program: Program<any>, signature: string) => {
+  const transaction = await program.provider.connection.getTransaction(
+    signature,
+    { commitment: "confirmed", maxSupportedTransactionVersion: 0 }
+  );
+
+  const eventParser = new EventParser(program.programId, program.coder);
+  return Array.from(
+    eventParser.parseLogs(transaction?.meta?.logMessages ?? [])
+  );
+};
+
+// resolve_market rejects markets until their betting period is over on chain
+export const waitForExpiry = async (
+  program: Program<any>,
+  market: PublicKey
+) => {
+  const { startTime, duration } = await program.account.market.fetch(market);
+  const expiry = (startTime as BN).add(duration as BN).toNumber();
+  const connection = program.provider.connection;
+
+  for (;;) {
+    const blockTime = await connection.getBlockTime(await connection.getSlot());
+    if (blockTime !== null && blockTime > expiry) {
+      return;
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  }
+};
+
+export const fetchComputeUnits = async (
+  program: Program<any>,
+  signature: string
+) => {
+  const transaction = await program.provider.connection.getTransaction(
+    signature,
+    { commitment: "confirmed", maxSupportedTransactionVersion: 0 }
+  );
+
+  return transaction?.meta?.computeUnitsConsumed ?? 0;
+};
+
+const bpsOf = (amount: BN, bps: number) => {
+  return amount.muln(bps).divn(10_000);
+};
+
+export const calculateTotalFees = (
+  pool: BN,
+  referredVolume: BN,
+  feeSchedule: {
+    protocolBps: number;
+    creatorBps: number;
+    referrerBps: number;
+    resolverBps: number;
+  }
+) => {
+  const unreferredVolume = pool.sub(referredVolume);
+
+  return bpsOf(pool, feeSchedule.protocolBps)
+    .add(bpsOf(unreferredVolume, feeSchedule.referrerBps))
+    .add(bpsOf(pool, feeSchedule.creatorBps))
+    .add(bpsOf(referredVolume, feeSchedule.referrerBps))
+    .add(bpsOf(pool, feeSchedule.resolverBps));
+};
+
+export const calculateFeeRebate = (
+  winningShares: BN,
+  totalWinningShares: BN,
+  pool: BN,
+  totalFees: BN,
+  discountBps: number
+) => {
+  const grossShare = winningShares.mul(pool).div(totalWinningShares);
+  const netShare = winningShares.mul(pool.sub(totalFees)).div(totalWinningShares);
+
+  return bpsOf(grossShare.sub(netShare), discountBps);
+};